@@ -0,0 +1,36 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use noise::{Perlin, Simplex};
+
+use bevy::prelude::*;
+
+use cheese_game::{Chunk, TerrainChunk, Vertex};
+
+const SEED: u32 = 54321;
+
+// a 50x50 chunk on the slope just downhill of the start, so every vertex samples the noise
+fn bench_chunk() -> TerrainChunk {
+    TerrainChunk::new(Chunk::new(
+        Vertex::new(0, -1),
+        Vertex::new(50, 50),
+        Vec2::ONE * 2.,
+    ))
+}
+
+fn noise_functions(c: &mut Criterion) {
+    let chunk = bench_chunk();
+    let mut group = c.benchmark_group("generate_mesh");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(chunk.chunk.count_vertices() as u64));
+    group.bench_with_input(BenchmarkId::new("Perlin", "50x50"), &chunk, |b, chunk| {
+        let noise = Perlin::new(SEED);
+        b.iter(|| chunk.generate_mesh(&noise));
+    });
+    group.bench_with_input(BenchmarkId::new("Simplex", "50x50"), &chunk, |b, chunk| {
+        let noise = Simplex::new(SEED);
+        b.iter(|| chunk.generate_mesh(&noise));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, noise_functions);
+criterion_main!(benches);
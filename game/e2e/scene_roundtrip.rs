@@ -0,0 +1,88 @@
+use serde::de::DeserializeSeed;
+
+use bevy::{prelude::*, scene::serde::SceneDeserializer};
+
+use cheese_game::{Chunk, EdgeBehavior, TerrainChunk, TerrainPlugin, Vertex, WorldEdges};
+
+fn chunks() -> Vec<TerrainChunk> {
+    vec![
+        TerrainChunk::new(Chunk::new(
+            Vertex::new(0, -1),
+            Vertex::new(50, 50),
+            Vec2::splat(2.),
+        )),
+        TerrainChunk::new(Chunk::new(
+            Vertex::new(-2, -3),
+            Vertex::new(25, 25),
+            Vec2::splat(4.),
+        ))
+        .with_noise_scale(Vec2::splat(2.))
+        .with_edges(WorldEdges::new(
+            Vertex::new(-100, -100),
+            Vertex::new(100, 100),
+            EdgeBehavior::Mirror,
+        )),
+        TerrainChunk::new(Chunk::new(
+            Vertex::new(3, 0),
+            Vertex::new(10, 20),
+            Vec2::new(1., 0.5),
+        ))
+        .with_blend_seeds(1234, 0.75),
+    ]
+}
+
+fn assert_same_chunk(loaded: &TerrainChunk, expected: &TerrainChunk) {
+    assert_eq!(loaded.chunk.origin, expected.chunk.origin);
+    assert_eq!(loaded.chunk.size, expected.chunk.size);
+    assert_eq!(loaded.chunk.quad_size, expected.chunk.quad_size);
+    assert_eq!(loaded.edges, expected.edges);
+    assert_eq!(loaded.noise_scale, expected.noise_scale);
+    assert_eq!(loaded.blend_seeds, expected.blend_seeds);
+}
+
+#[test]
+fn terrain_chunks_survive_scene_round_trip() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TerrainPlugin));
+
+    let expected = chunks();
+    let entities = expected
+        .iter()
+        .map(|chunk| app.world.spawn(chunk.clone()).id())
+        .collect::<Vec<_>>();
+    let registry = app.world.resource::<AppTypeRegistry>().clone();
+
+    let scene = DynamicSceneBuilder::from_world(&app.world)
+        .extract_entities(entities.into_iter())
+        .build();
+    let serialized = scene
+        .serialize_ron(&registry)
+        .expect("terrain chunks should serialize");
+
+    let mut deserializer =
+        ron::de::Deserializer::from_str(&serialized).expect("the scene should be valid ron");
+    let scene = SceneDeserializer {
+        type_registry: &registry.read(),
+    }
+    .deserialize(&mut deserializer)
+    .expect("terrain chunks should deserialize");
+
+    let mut world = World::new();
+    world.insert_resource(registry);
+    scene
+        .write_to_world(&mut world, &mut Default::default())
+        .expect("the scene should write to a fresh world");
+
+    let mut loaded = world
+        .query::<&TerrainChunk>()
+        .iter(&world)
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(loaded.len(), expected.len());
+    loaded.sort_by_key(|chunk| (chunk.chunk.origin.x, chunk.chunk.origin.z));
+    let mut expected = expected;
+    expected.sort_by_key(|chunk| (chunk.chunk.origin.x, chunk.chunk.origin.z));
+    for (loaded, expected) in loaded.iter().zip(expected.iter()) {
+        assert_same_chunk(loaded, expected);
+    }
+}
@@ -1,3 +1,4 @@
+use derive_more::Display;
 use itertools::Itertools;
 
 use bevy::prelude::*;
@@ -6,7 +7,9 @@ use super::Vertex;
 
 // Handles chunking in 2D vertices using a consistent grid of `Vertex` in quad_size units
 #[derive(Debug, Clone)]
+#[derive(Display)]
 #[derive(Reflect)]
+#[display(fmt = "chunk {} of size {}", origin, size)]
 pub struct Chunk {
     // the length of the chunk in vertices
     pub size: Vertex,
@@ -25,16 +28,25 @@ impl Chunk {
         }
     }
 
-    // gets the nearest chunk to a given translation
+    // gets the chunk whose footprint contains a given translation
     pub fn from_translation(translation: Vec3, chunk_size: Vertex, quad_size: Vec2) -> Self {
-        let origin = Vertex::from_translation(
-            translation,
-            // scale quads by chunk_size to get position in chunk units rather than grid units
-            quad_size * Vec2::new(chunk_size.x as f32, chunk_size.z as f32),
-        );
+        let origin = Self::origin_containing(translation, chunk_size, quad_size);
         Self::new(origin, chunk_size, quad_size)
     }
 
+    // gets the origin (in chunk units) of the chunk whose footprint contains a translation
+    pub fn origin_containing(translation: Vec3, chunk_size: Vertex, quad_size: Vec2) -> Vertex {
+        let chunk_length = Vec2::new(
+            chunk_size.x as f32 * quad_size.x,
+            chunk_size.z as f32 * quad_size.y,
+        );
+        Vertex {
+            x: (translation.x / chunk_length.x).floor() as i32,
+            // chunks extend towards +Z from their offset, which is at -origin.z
+            z: -(translation.z / chunk_length.y).floor() as i32,
+        }
+    }
+
     pub fn count_vertices(&self) -> i32 {
         (self.size.x + 1) * (self.size.z + 1)
     }
@@ -17,19 +17,29 @@ pub use vertex::*;
 pub struct Level {
     // in Chunk units
     pub chunks_in_play: HashSet<Vertex>,
+    // the ring of chunks just outside of play, which can be prepared ahead of time
+    pub chunks_to_preload: HashSet<Vertex>,
     // in Grid units
     pub chunk_size: Vertex,
     pub quad_size: Vec2,
+    // the position the level is centered around
+    pub focus: Vec3,
 }
 
 impl Level {
     const VISIBLE_CHUNKS_RANGE: (i32, i32) = (3, 3);
+    const PRELOAD_CHUNKS_RANGE: (i32, i32) = (
+        Self::VISIBLE_CHUNKS_RANGE.0 + 1,
+        Self::VISIBLE_CHUNKS_RANGE.1 + 1,
+    );
 
     pub fn new(chunk_size: Vertex, quad_size: Vec2) -> Self {
         Self {
             chunks_in_play: HashSet::default(),
+            chunks_to_preload: HashSet::default(),
             chunk_size,
             quad_size,
+            focus: Vec3::ZERO,
         }
     }
 
@@ -38,32 +48,34 @@ impl Level {
     }
 
     pub fn update(&mut self, cheese_position: Vec3) {
+        self.focus = cheese_position;
         let cheese_chunk =
             Chunk::from_translation(cheese_position, self.chunk_size, self.quad_size);
 
-        let left_edge = cheese_chunk
-            .origin
-            .x
-            .saturating_sub(Self::VISIBLE_CHUNKS_RANGE.0);
-        let right_edge = cheese_chunk
-            .origin
-            .x
-            .saturating_add(Self::VISIBLE_CHUNKS_RANGE.0);
-        let forward_edge = cheese_chunk
-            .origin
-            .z
-            .saturating_add(Self::VISIBLE_CHUNKS_RANGE.1);
-        let backward_edge = cheese_chunk
-            .origin
-            .z
-            .saturating_sub(Self::VISIBLE_CHUNKS_RANGE.1);
-
         self.chunks_in_play.clear();
-        for (x, y) in (left_edge..=right_edge).cartesian_product(backward_edge..=forward_edge) {
-            let chunk_vertex = (x, y).into();
-            self.chunks_in_play.insert(chunk_vertex);
+        self.chunks_in_play.extend(Self::chunks_in_range(
+            cheese_chunk.origin,
+            Self::VISIBLE_CHUNKS_RANGE,
+        ));
+
+        self.chunks_to_preload.clear();
+        for chunk_vertex in Self::chunks_in_range(cheese_chunk.origin, Self::PRELOAD_CHUNKS_RANGE) {
+            if !self.chunks_in_play.contains(&chunk_vertex) {
+                self.chunks_to_preload.insert(chunk_vertex);
+            }
         }
     }
+
+    fn chunks_in_range(center: Vertex, range: (i32, i32)) -> impl Iterator<Item = Vertex> {
+        let left_edge = center.x.saturating_sub(range.0);
+        let right_edge = center.x.saturating_add(range.0);
+        let forward_edge = center.z.saturating_add(range.1);
+        let backward_edge = center.z.saturating_sub(range.1);
+
+        (left_edge..=right_edge)
+            .cartesian_product(backward_edge..=forward_edge)
+            .map(|(x, z)| Vertex::new(x, z))
+    }
 }
 
 impl Default for Level {
@@ -1,10 +1,12 @@
-use derive_more::{Add, AddAssign, From, Mul, MulAssign, Sub, SubAssign};
+use derive_more::{Add, AddAssign, Display, From, Mul, MulAssign, Sub, SubAssign};
 
 use bevy::prelude::*;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[derive(Add, AddAssign, Mul, MulAssign, Sub, SubAssign, From)]
+#[derive(Display)]
 #[derive(Reflect)]
+#[display(fmt = "({}, {})", x, z)]
 pub struct Vertex {
     pub x: i32,
     pub z: i32,
@@ -18,9 +18,15 @@ pub use obstacles::*;
 mod person;
 pub use person::*;
 
+mod sandstorm;
+pub use sandstorm::*;
+
 mod score;
 pub use score::*;
 
+mod seed;
+pub use seed::*;
+
 mod terrain;
 pub use terrain::*;
 
@@ -39,6 +45,12 @@ impl Plugin for CheeseRacePlugin {
                 )
                     .run_if(in_state(AppState::Racing).or_else(in_state(AppState::SpawningScene))),
             )
-            .add_plugins((LevelPlugin, PersonPlugin, CheesePlugin, ScorePlugin));
+            .add_plugins((
+                LevelPlugin,
+                PersonPlugin,
+                CheesePlugin,
+                ScorePlugin,
+                SandstormPlugin,
+            ));
     }
 }
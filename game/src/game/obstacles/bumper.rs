@@ -0,0 +1,64 @@
+use bevy_xpbd_3d::components::{Collider, RigidBody};
+
+use bevy::prelude::*;
+
+use crate::{Chunk, Vertex};
+
+// A post that launches the cheese away from its center on contact
+#[derive(Clone, Copy, Debug)]
+#[derive(Component, Reflect)]
+pub struct CheeseBumper {
+    pub impulse_strength: f32,
+}
+
+impl Default for CheeseBumper {
+    fn default() -> Self {
+        Self::new(4000.)
+    }
+}
+
+impl CheeseBumper {
+    const HEIGHT: f32 = 4.;
+    pub const RADIUS: f32 = 1.5;
+
+    pub fn new(impulse_strength: f32) -> Self {
+        Self { impulse_strength }
+    }
+
+    // stands the bumper upright on the slope at a chunk's local vertex
+    pub fn slope_translation(chunk: &Chunk, vertex: Vertex) -> Vec3 {
+        let grid_position = chunk.to_translation(chunk.to_global_coords(vertex));
+        Vec3::new(
+            grid_position.x,
+            -grid_position.y + Self::HEIGHT / 2.,
+            grid_position.y,
+        )
+    }
+
+    pub fn to_bundle(
+        self,
+        translation: Vec3,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> impl Bundle {
+        (
+            Name::new("Bumper"),
+            RigidBody::Static,
+            Collider::cylinder(Self::HEIGHT, Self::RADIUS),
+            PbrBundle {
+                mesh: meshes.add(
+                    shape::Cylinder {
+                        height: Self::HEIGHT,
+                        radius: Self::RADIUS,
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
+                material: materials.add(Color::rgb(0.85, 0.2, 0.25).into()),
+                transform: Transform::from_translation(translation),
+                ..Default::default()
+            },
+            self,
+        )
+    }
+}
@@ -3,16 +3,22 @@ use rand::Rng;
 
 use bevy::{prelude::*, utils::HashMap};
 
-use crate::{Level, TextureAssets, Vertex};
+use crate::{Level, TextureAssets, Vertex, WorldSeed};
 
 use super::Chunk;
 
+mod bumper;
+pub use bumper::*;
+
 mod noise;
 pub use noise::*;
 
 mod plugin;
 pub use plugin::*;
 
+mod tornado;
+pub use tornado::*;
+
 mod wall;
 pub use wall::*;
 
@@ -31,54 +37,83 @@ impl Obstacles {
         }
     }
 
-    pub fn generate_obstacles_for_chunk<'a>(
-        &'a self,
-        chunk: Chunk,
+    // the vertices of a chunk that are checked for obstacles, spaced by a random stride from
+    // `strides`, along with the obstacle noise at each of them
+    fn candidate_vertices<'a>(
+        chunk: &Chunk,
+        mut rng: impl Rng,
+        strides: std::ops::Range<i32>,
         noise: &'a impl NoiseFn<f64, 2>,
-        textures: &'a TextureAssets,
-        meshes: &'a mut Assets<Mesh>,
-        materials: &'a mut Assets<StandardMaterial>,
-    ) -> impl Iterator<Item = impl Bundle> + 'a {
-        let mut rng = rand::thread_rng();
-        let before_first_chunk = chunk.origin.z <= 0;
-        let before_fifth_chunk = chunk.origin.z < 5;
+    ) -> impl Iterator<Item = (Vertex, f64)> + 'a {
+        let chunk = chunk.clone();
+        // nothing is placed on the hilltop or the first chunk of the slope
+        let num_candidates = if chunk.origin.z <= 0 {
+            0
+        } else {
+            // don't iterate along the final edge
+            ((chunk.size.z - 1) * chunk.size.x) as usize
+        };
         chunk
             .iter_by_row()
-            // don't iterate along the final edge
-            .take(((chunk.size.z - 1) * chunk.size.x) as usize)
+            .take(num_candidates)
             // skip by some arbitrary amount
-            .step_by(rng.gen_range((chunk.size.x * 4)..(chunk.size.x * 4 + 7)) as usize)
-            // and make a wall there if the noise value is high enough
-            .filter_map(move |vertex| {
-                if before_first_chunk {
-                    return None;
-                }
-                let global_vertex = chunk.to_global_coords(vertex);
-                let position = chunk.to_translation(global_vertex);
-                let noise = noise.get([position.x as f64, position.y as f64]);
-                let noise_threshold = if before_fifth_chunk { 0.995 } else { 0.98 };
-                if noise > noise_threshold {
-                    info!("{:?} {} {}", global_vertex, position, noise);
-                    Some(
-                        Wall::new(
-                            chunk.clone(),
-                            vertex,
-                            Vec2::new(
-                                chunk.quad_size.x * chunk.size.x as f32 / 2.,
-                                chunk.quad_size.y,
-                            ),
-                        )
-                        .to_bundle(textures, meshes, materials),
-                    )
-                } else {
-                    None
-                }
+            .step_by(rng.gen_range(strides) as usize)
+            .map(move |vertex| {
+                let position = chunk.to_translation(chunk.to_global_coords(vertex));
+                (vertex, noise.get([position.x as f64, position.y as f64]))
+            })
+    }
+
+    // makes a wall wherever the noise value is high enough
+    pub fn generate_walls_for_chunk(
+        chunk: &Chunk,
+        seed: WorldSeed,
+        noise: &impl NoiseFn<f64, 2>,
+    ) -> Vec<Wall> {
+        let noise_threshold = if chunk.origin.z < 5 { 0.995 } else { 0.98 };
+        let strides = (chunk.size.x * 4)..(chunk.size.x * 4 + 7);
+        Self::candidate_vertices(chunk, seed.chunk_rng(chunk.origin, 0), strides, noise)
+            .filter(|(_, noise)| *noise > noise_threshold)
+            .map(|(vertex, noise)| {
+                info!("{:?} {}", chunk.to_global_coords(vertex), noise);
+                Wall::new(
+                    chunk.clone(),
+                    vertex,
+                    Vec2::new(
+                        chunk.quad_size.x * chunk.size.x as f32 / 2.,
+                        chunk.quad_size.y,
+                    ),
+                )
             })
+            .collect()
     }
 
+    // places bumpers where the noise is high, but not quite high enough for a wall,
+    // leaving out any that would stand inside one of the chunk's walls
+    pub fn generate_bumpers_for_chunk(
+        chunk: &Chunk,
+        seed: WorldSeed,
+        noise: &impl NoiseFn<f64, 2>,
+        walls: &[Wall],
+    ) -> Vec<Vec3> {
+        // bumpers are smaller than walls, so check more often
+        let strides = (chunk.size.x * 2)..(chunk.size.x * 2 + 5);
+        Self::candidate_vertices(chunk, seed.chunk_rng(chunk.origin, 1), strides, noise)
+            .filter(|(_, noise)| (0.93..0.96).contains(noise))
+            .map(|(vertex, _)| CheeseBumper::slope_translation(chunk, vertex))
+            .filter(|translation| {
+                !walls
+                    .iter()
+                    .any(|wall| wall.contains(*translation, CheeseBumper::RADIUS))
+            })
+            .collect()
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn update(
         &mut self,
         level: &Level,
+        seed: WorldSeed,
         noise: &impl NoiseFn<f64, 2>,
         commands: &mut Commands,
         textures: &TextureAssets,
@@ -116,12 +151,18 @@ impl Obstacles {
                     origin: Vertex::new(origin.x, -origin.z),
                 };
                 let mut chunk_entities = vec![];
-                for bundle in
-                    self.generate_obstacles_for_chunk(chunk, noise, textures, meshes, materials)
-                {
+                let walls = Self::generate_walls_for_chunk(&chunk, seed, noise);
+                for translation in Self::generate_bumpers_for_chunk(&chunk, seed, noise, &walls) {
+                    let bundle = CheeseBumper::default().to_bundle(translation, meshes, materials);
                     let entity = commands.spawn(bundle).id();
                     chunk_entities.push(entity)
                 }
+                for wall in walls {
+                    let entity = commands
+                        .spawn(wall.to_bundle(textures, meshes, materials))
+                        .id();
+                    chunk_entities.push(entity)
+                }
                 self.chunk_entities.insert(*origin, chunk_entities);
             }
         }
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{AppState, ObstacleNoise};
+use crate::{despawn_all_recursive, AppState, CheeseTornado, ObstacleNoise, WorldSeed};
 
 mod systems;
 
@@ -9,11 +9,26 @@ pub struct ObstaclesPlugin;
 
 impl Plugin for ObstaclesPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            systems::update_obstacles.run_if(resource_exists::<ObstacleNoise>()),
-        )
-        .add_systems(OnEnter(AppState::SpawningScene), systems::seed_noise)
-        .add_systems(Update, systems::attach_obstacles);
+        app.init_resource::<WorldSeed>()
+            .add_systems(
+                Update,
+                systems::update_obstacles.run_if(resource_exists::<ObstacleNoise>()),
+            )
+            .add_systems(OnEnter(AppState::SpawningScene), systems::seed_noise)
+            .add_systems(Update, (systems::attach_obstacles, systems::bump_cheese))
+            .add_systems(OnEnter(AppState::Racing), systems::spawn_tornado)
+            .add_systems(
+                Update,
+                (
+                    systems::move_tornadoes,
+                    systems::fling_bodies,
+                    systems::spin_tornado_dust,
+                )
+                    .run_if(in_state(AppState::Racing)),
+            )
+            .add_systems(
+                OnExit(AppState::GameOver),
+                despawn_all_recursive::<CheeseTornado>,
+            );
     }
 }
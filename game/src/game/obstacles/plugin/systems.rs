@@ -1,9 +1,13 @@
 use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
 
-use crate::{Level, ObstacleNoise, Obstacles, TextureAssets};
+use crate::{
+    Cheese, CheeseBumper, CheeseTornado, Level, ObstacleNoise, Obstacles, TextureAssets,
+    TornadoDust, WorldSeed,
+};
 
-pub(super) fn seed_noise(mut commands: Commands) {
-    commands.insert_resource(ObstacleNoise::new(rand::random()));
+pub(super) fn seed_noise(mut commands: Commands, seed: Res<WorldSeed>) {
+    commands.insert_resource(ObstacleNoise::new(seed.obstacle_seed()));
 }
 
 pub(super) fn attach_obstacles(mut commands: Commands, query: Query<Entity, Added<Level>>) {
@@ -15,6 +19,7 @@ pub(super) fn attach_obstacles(mut commands: Commands, query: Query<Entity, Adde
 pub(super) fn update_obstacles(
     mut commands: Commands,
     mut obstacles_query: Query<(&mut Obstacles, &Level)>,
+    seed: Res<WorldSeed>,
     noise: Res<ObstacleNoise>,
     textures: Res<TextureAssets>,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -23,6 +28,7 @@ pub(super) fn update_obstacles(
     for (mut obstacles, level) in obstacles_query.iter_mut() {
         obstacles.update(
             level,
+            *seed,
             &noise.get(),
             &mut commands,
             &textures,
@@ -31,3 +37,100 @@ pub(super) fn update_obstacles(
         );
     }
 }
+
+pub(super) fn bump_cheese(
+    mut collisions: EventReader<CollisionStarted>,
+    bumper_query: Query<(&CheeseBumper, &GlobalTransform)>,
+    mut cheese_query: Query<(&Transform, &mut ExternalImpulse), With<Cheese>>,
+) {
+    for CollisionStarted(entity1, entity2) in collisions.read() {
+        let (bumper_entity, cheese_entity) = if bumper_query.contains(*entity1) {
+            (*entity1, *entity2)
+        } else {
+            (*entity2, *entity1)
+        };
+        let Ok((bumper, bumper_transform)) = bumper_query.get(bumper_entity) else {
+            continue;
+        };
+        let Ok((cheese_transform, mut impulse)) = cheese_query.get_mut(cheese_entity) else {
+            continue;
+        };
+        let direction =
+            (cheese_transform.translation - bumper_transform.translation()).normalize_or_zero();
+        impulse.apply_impulse(direction * bumper.impulse_strength);
+    }
+}
+
+pub(super) fn spawn_tornado(
+    mut commands: Commands,
+    cheese_query: Query<&Transform, With<Cheese>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Ok(cheese_transform) = cheese_query.get_single() else {
+        return;
+    };
+    let tornado = CheeseTornado::new(CheeseTornado::random_path(cheese_transform.translation));
+    let dust = tornado.dust_bundle(&mut meshes, &mut materials);
+    commands
+        .spawn(tornado.to_bundle(&mut meshes, &mut materials))
+        .with_children(|builder| {
+            builder.spawn(dust);
+        });
+}
+
+pub(super) fn move_tornadoes(
+    mut tornado_query: Query<(&mut CheeseTornado, &mut Transform)>,
+    cheese_query: Query<&Transform, (With<Cheese>, Without<CheeseTornado>)>,
+    time: Res<Time>,
+) {
+    for (mut tornado, mut transform) in tornado_query.iter_mut() {
+        tornado.t += tornado.speed * time.delta_seconds();
+        match tornado.ground_position() {
+            Some(position) => {
+                transform.translation = CheeseTornado::funnel_translation(position);
+            }
+            None => {
+                // the route is finished, so start a new one near the cheese
+                let Ok(cheese_transform) = cheese_query.get_single() else {
+                    continue;
+                };
+                tornado.path = CheeseTornado::random_path(cheese_transform.translation);
+                tornado.t = 0.;
+            }
+        }
+    }
+}
+
+pub(super) fn fling_bodies(
+    tornado_query: Query<(&CheeseTornado, &GlobalTransform)>,
+    mut body_query: Query<(&RigidBody, &Transform, &mut ExternalImpulse)>,
+    time: Res<Time>,
+) {
+    for (tornado, tornado_transform) in tornado_query.iter() {
+        let center = tornado_transform.translation();
+        for (body, transform, mut impulse) in body_query.iter_mut() {
+            if *body != RigidBody::Dynamic {
+                continue;
+            }
+            let offset = transform.translation - center;
+            let horizontal_distance = Vec2::new(offset.x, offset.z).length();
+            if horizontal_distance > tornado.radius {
+                continue;
+            }
+            let outward = Vec3::new(offset.x, 0., offset.z).normalize_or_zero();
+            // a constant force applied over this frame
+            let force = (outward + Vec3::Y) * tornado.strength;
+            impulse.apply_impulse(force * time.delta_seconds());
+        }
+    }
+}
+
+pub(super) fn spin_tornado_dust(
+    mut dust_query: Query<&mut Transform, With<TornadoDust>>,
+    time: Res<Time>,
+) {
+    for mut transform in dust_query.iter_mut() {
+        transform.rotate_y(4. * time.delta_seconds());
+    }
+}
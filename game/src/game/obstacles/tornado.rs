@@ -0,0 +1,128 @@
+use rand::Rng;
+
+use bevy::prelude::*;
+
+// A twister that wanders across the hill, flinging anything loose into the air
+#[derive(Clone, Debug)]
+#[derive(Component, Reflect)]
+pub struct CheeseTornado {
+    pub radius: f32,
+    pub strength: f32,
+    pub path: Vec<Vec3>,
+    pub speed: f32,
+    // how far along the path the tornado has traveled, in world units
+    pub t: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct TornadoDust;
+
+impl CheeseTornado {
+    const HEIGHT: f32 = 30.;
+    const PATH_POINTS: usize = 4;
+
+    pub fn new(path: Vec<Vec3>) -> Self {
+        Self {
+            radius: 12.,
+            strength: 6000.,
+            path,
+            speed: 15.,
+            t: 0.,
+        }
+    }
+
+    // builds a route that crosses the hill ahead of `target`, from one side to the other
+    pub fn random_path(target: Vec3) -> Vec<Vec3> {
+        let mut rng = rand::thread_rng();
+        let side = if rng.gen_bool(0.5) { 1. } else { -1. };
+        let ahead = rng.gen_range(80.0..160.0);
+        (0..Self::PATH_POINTS)
+            .map(|index| {
+                let progress = index as f32 / (Self::PATH_POINTS - 1) as f32;
+                let x = target.x + side * (60. - 120. * progress) + rng.gen_range(-10.0..10.0);
+                let z = target.z + ahead + 40. * progress + rng.gen_range(-10.0..10.0);
+                // the hill drops one unit for every unit traveled downhill
+                Vec3::new(x, -z, z)
+            })
+            .collect()
+    }
+
+    // gets the point on the ground along the path, or None once the path is complete
+    pub fn ground_position(&self) -> Option<Vec3> {
+        let mut remaining = self.t;
+        for (start, end) in self.path.iter().zip(self.path.iter().skip(1)) {
+            let length = start.distance(*end);
+            if remaining <= length {
+                return Some(start.lerp(*end, remaining / length.max(f32::EPSILON)));
+            }
+            remaining -= length;
+        }
+        None
+    }
+
+    // the funnel is centered above its point on the ground
+    pub fn funnel_translation(ground_position: Vec3) -> Vec3 {
+        ground_position + Vec3::Y * Self::HEIGHT / 2.
+    }
+
+    pub fn to_bundle(
+        self,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> impl Bundle {
+        let translation = Self::funnel_translation(self.path.first().copied().unwrap_or_default());
+        (
+            Name::new("Tornado"),
+            PbrBundle {
+                // there is no cone shape in bevy yet, so the funnel is a plain column
+                mesh: meshes.add(
+                    shape::Cylinder {
+                        height: Self::HEIGHT,
+                        radius: self.radius,
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::rgba(0.6, 0.55, 0.5, 0.35),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..Default::default()
+                }),
+                transform: Transform::from_translation(translation),
+                ..Default::default()
+            },
+            self,
+        )
+    }
+
+    pub fn dust_bundle(
+        &self,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> impl Bundle {
+        (
+            Name::new("Tornado Dust"),
+            TornadoDust,
+            PbrBundle {
+                mesh: meshes.add(
+                    shape::Torus {
+                        radius: self.radius,
+                        ring_radius: self.radius / 6.,
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::rgba(0.55, 0.45, 0.35, 0.5),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..Default::default()
+                }),
+                transform: Transform::from_xyz(0., -Self::HEIGHT / 2., 0.),
+                ..Default::default()
+            },
+        )
+    }
+}
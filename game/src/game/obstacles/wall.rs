@@ -23,6 +23,31 @@ impl Wall {
         }
     }
 
+    // the wall stands on the slope, leaning back into the hill
+    pub fn transform(&self) -> Transform {
+        let grid_position = self
+            .chunk
+            .to_translation(self.chunk.to_global_coords(self.vertex));
+        let sloped_translation = Vec3::new(
+            grid_position.x + self.size.x / 2.,
+            -grid_position.y + Self::HEIGHT * 0.45,
+            grid_position.y,
+        );
+        Transform::from_translation(sloped_translation)
+            .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_8))
+    }
+
+    // whether a point is inside the wall's box, grown by `margin` on every side
+    pub fn contains(&self, point: Vec3, margin: f32) -> bool {
+        let local = self
+            .transform()
+            .compute_affine()
+            .inverse()
+            .transform_point3(point);
+        let half_extents = Vec3::new(self.size.x, Self::HEIGHT, self.size.y) / 2. + margin;
+        local.abs().cmple(half_extents).all()
+    }
+
     pub fn to_bundle(
         self,
         textures: &TextureAssets,
@@ -30,12 +55,7 @@ impl Wall {
         materials: &mut Assets<StandardMaterial>,
     ) -> impl Bundle {
         let global_vertex = self.chunk.to_global_coords(self.vertex);
-        let grid_position = self.chunk.to_translation(global_vertex);
-        let sloped_translation = Vec3::new(
-            grid_position.x + self.size.x / 2.,
-            -grid_position.y + Self::HEIGHT * 0.45,
-            grid_position.y,
-        );
+        let transform = self.transform();
         (
             Name::new(format!("Wall ({},{})", global_vertex.x, global_vertex.z)),
             RigidBody::Static,
@@ -47,8 +67,7 @@ impl Wall {
                     base_color_texture: Some(textures.bricks.clone()),
                     ..Default::default()
                 }),
-                transform: Transform::from_translation(sloped_translation)
-                    .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_8)),
+                transform,
                 ..Default::default()
             },
             self,
@@ -0,0 +1,171 @@
+use rand::Rng;
+
+use bevy::prelude::*;
+
+use crate::PlayerCamera;
+
+// A storm of sand that blows around the camera and hides distant terrain while the resource exists
+#[derive(Clone, Copy, Debug)]
+#[derive(Resource, Reflect)]
+pub struct SandstormConfig {
+    pub intensity: f32,
+    pub particle_count: u32,
+    pub velocity: Vec3,
+}
+
+impl Default for SandstormConfig {
+    fn default() -> Self {
+        Self {
+            intensity: 2.,
+            particle_count: 400,
+            velocity: Vec3::new(12., 0., -4.),
+        }
+    }
+}
+
+impl SandstormConfig {
+    // how far particles are spread from the camera before they wrap around
+    const RADIUS: f32 = 40.;
+
+    // the camera's view distance during the storm
+    pub fn view_distance(&self) -> f32 {
+        self.intensity * 50.
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct SandParticle;
+
+// The view distance a camera had before the storm shortened it
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct SandstormFarOverride {
+    pub original_far: f32,
+}
+
+pub struct SandstormPlugin;
+
+impl Plugin for SandstormPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_sand_particles.run_if(resource_added::<SandstormConfig>()),
+                (blow_sand_particles, shorten_view_distance)
+                    .run_if(resource_exists::<SandstormConfig>()),
+                end_sandstorm.run_if(resource_removed::<SandstormConfig>()),
+            ),
+        );
+    }
+}
+
+fn random_offset(rng: &mut impl Rng) -> Vec3 {
+    let direction = Vec3::new(
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(-1.0..1.0),
+    )
+    .normalize_or_zero();
+    direction * SandstormConfig::RADIUS * rng.gen_range(0.0f32..1.0).cbrt()
+}
+
+fn spawn_sand_particles(
+    mut commands: Commands,
+    config: Res<SandstormConfig>,
+    camera_query: Query<&Transform, With<PlayerCamera>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let center = camera_query
+        .get_single()
+        .map(|transform| transform.translation)
+        .unwrap_or_default();
+    let mesh = meshes.add(shape::Quad::new(Vec2::splat(0.15)).into());
+    let material = materials.add(StandardMaterial {
+        base_color: Color::rgba(0.85, 0.7, 0.45, 0.7),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        double_sided: true,
+        cull_mode: None,
+        ..Default::default()
+    });
+    let mut rng = rand::thread_rng();
+    for _ in 0..config.particle_count {
+        commands.spawn((
+            Name::new("Sand Particle"),
+            SandParticle,
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(center + random_offset(&mut rng)),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+fn blow_sand_particles(
+    config: Res<SandstormConfig>,
+    camera_query: Query<&Transform, (With<PlayerCamera>, Without<SandParticle>)>,
+    mut particle_query: Query<&mut Transform, With<SandParticle>>,
+    time: Res<Time>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let center = camera_transform.translation;
+    for mut transform in particle_query.iter_mut() {
+        transform.translation += config.velocity * time.delta_seconds();
+        let offset = transform.translation - center;
+        // particles blown out of the sphere re-enter on the opposite side
+        if offset.length() > SandstormConfig::RADIUS {
+            transform.translation = center - offset.normalize() * SandstormConfig::RADIUS * 0.99;
+        }
+        // billboard the particles towards the camera
+        transform.look_at(center, Vec3::Y);
+    }
+}
+
+fn shorten_view_distance(
+    mut commands: Commands,
+    config: Res<SandstormConfig>,
+    mut camera_query: Query<
+        (Entity, &mut Projection, Option<&SandstormFarOverride>),
+        With<PlayerCamera>,
+    >,
+) {
+    let view_distance = config.view_distance();
+    for (entity, mut projection, far_override) in camera_query.iter_mut() {
+        let Projection::Perspective(perspective) = projection.as_ref() else {
+            continue;
+        };
+        if perspective.far == view_distance {
+            continue;
+        }
+        if far_override.is_none() {
+            commands.entity(entity).insert(SandstormFarOverride {
+                original_far: perspective.far,
+            });
+        }
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.far = view_distance;
+        }
+    }
+}
+
+fn end_sandstorm(
+    mut commands: Commands,
+    particle_query: Query<Entity, With<SandParticle>>,
+    mut camera_query: Query<(Entity, &mut Projection, &SandstormFarOverride)>,
+) {
+    for entity in particle_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    for (entity, mut projection, far_override) in camera_query.iter_mut() {
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.far = far_override.original_far;
+        }
+        commands.entity(entity).remove::<SandstormFarOverride>();
+    }
+}
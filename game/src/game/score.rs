@@ -1,13 +1,40 @@
+use derive_more::Display;
+
 use bevy::prelude::*;
 
 use crate::{despawn_all_recursive, AppState, Cheese};
 
 #[derive(Clone, Copy, Debug, Default)]
-#[derive(Resource)]
+#[derive(Display, Resource)]
+#[display(fmt = "{:.0}", _0)]
 pub struct Score(pub f32);
 #[derive(Clone, Copy, Debug, Default)]
-#[derive(Resource)]
+#[derive(Display, Resource)]
+#[display(fmt = "{:.0}", _0)]
 pub struct HighScore(pub f32);
+// in-game time elapsed in the current race, which only runs while racing
+#[derive(Clone, Copy, Debug)]
+#[derive(Resource)]
+pub struct CheeseClock {
+    pub total_seconds: f64,
+    pub paused: bool,
+}
+
+impl Default for CheeseClock {
+    fn default() -> Self {
+        Self {
+            total_seconds: 0.,
+            paused: true,
+        }
+    }
+}
+
+impl std::fmt::Display for CheeseClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seconds = self.total_seconds as u64;
+        write!(f, "{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
 
 #[derive(Clone, Copy, Debug)]
 #[derive(Component)]
@@ -15,6 +42,9 @@ pub struct ScoreUI;
 #[derive(Clone, Copy, Debug)]
 #[derive(Component)]
 pub struct ScoreText;
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct ClockText;
 
 pub struct ScorePlugin;
 
@@ -22,14 +52,23 @@ impl Plugin for ScorePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Score>()
             .init_resource::<HighScore>()
+            .init_resource::<CheeseClock>()
             .add_systems(OnEnter(AppState::SpawningScene), render_score_ui)
+            .add_systems(OnEnter(AppState::Racing), resume_clock)
             .add_systems(
                 Update,
-                (track_score, track_score_ui).run_if(in_state(AppState::Racing)),
+                (
+                    tick_clock,
+                    (track_score, track_score_ui, track_clock_ui)
+                        .run_if(in_state(AppState::Racing)),
+                ),
             )
             .add_systems(
                 OnExit(AppState::Racing),
-                (apply_deferred, despawn_all_recursive::<ScoreUI>).chain(),
+                (
+                    pause_clock,
+                    (apply_deferred, despawn_all_recursive::<ScoreUI>).chain(),
+                ),
             )
             .add_systems(OnExit(AppState::GameOver), update_scores);
     }
@@ -43,11 +82,30 @@ fn track_score(mut score: ResMut<Score>, cheese_query: Query<&Transform, With<Ch
     score.0 = transform.translation.z - 50.;
 }
 
-fn update_scores(mut score: ResMut<Score>, mut high_score: ResMut<HighScore>) {
+fn tick_clock(mut clock: ResMut<CheeseClock>, time: Res<Time>) {
+    if !clock.paused {
+        clock.total_seconds += time.delta_seconds_f64();
+    }
+}
+
+fn resume_clock(mut clock: ResMut<CheeseClock>) {
+    clock.paused = false;
+}
+
+fn pause_clock(mut clock: ResMut<CheeseClock>) {
+    clock.paused = true;
+}
+
+fn update_scores(
+    mut score: ResMut<Score>,
+    mut high_score: ResMut<HighScore>,
+    mut clock: ResMut<CheeseClock>,
+) {
     if score.0 > high_score.0 {
         high_score.0 = score.0;
     }
     score.0 = 0.;
+    *clock = CheeseClock::default();
 }
 
 fn render_score_ui(mut commands: Commands) {
@@ -59,6 +117,8 @@ fn render_score_ui(mut commands: Commands) {
                 style: Style {
                     position_type: PositionType::Absolute,
                     right: Val::Percent(1.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::FlexEnd,
                     ..Default::default()
                 },
                 ..Default::default()
@@ -76,6 +136,17 @@ fn render_score_ui(mut commands: Commands) {
                     },
                 ),
             ));
+            builder.spawn((
+                Name::new("Clock Text"),
+                ClockText,
+                TextBundle::from_section(
+                    "0:00",
+                    TextStyle {
+                        font_size: 32.,
+                        ..Default::default()
+                    },
+                ),
+            ));
         });
 }
 
@@ -83,5 +154,12 @@ fn track_score_ui(mut ui_query: Query<&mut Text, With<ScoreText>>, score: Res<Sc
     let Ok(mut text) = ui_query.get_single_mut() else {
         return;
     };
-    text.sections[0].value = format!("{:.0}", score.0);
+    text.sections[0].value = score.to_string();
+}
+
+fn track_clock_ui(mut ui_query: Query<&mut Text, With<ClockText>>, clock: Res<CheeseClock>) {
+    let Ok(mut text) = ui_query.get_single_mut() else {
+        return;
+    };
+    text.sections[0].value = clock.to_string();
 }
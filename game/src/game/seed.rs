@@ -0,0 +1,41 @@
+use derive_more::Display;
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use bevy::prelude::*;
+
+use crate::Vertex;
+
+// the seed shared by all procedural generation in a race
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Display, Resource)]
+pub struct WorldSeed(pub u32);
+
+impl WorldSeed {
+    pub fn random() -> Self {
+        Self(rand::random())
+    }
+
+    // obstacles build the same kind of noise as the terrain, so they get their own seed
+    // to keep their placement from following the terrain's ridges
+    pub fn obstacle_seed(self) -> u32 {
+        self.0.wrapping_mul(0x9E37_79B9) ^ 0x5BD1_E995
+    }
+
+    // a random stream that is the same every time the chunk at `origin` is generated with this
+    // seed, with `stream` telling apart the generators that share a chunk
+    pub fn chunk_rng(self, origin: Vertex, stream: u64) -> StdRng {
+        let state = [origin.x as u32 as u64, origin.z as u32 as u64, stream]
+            .into_iter()
+            .fold(u64::from(self.0), |state, value| {
+                (state ^ value).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            });
+        StdRng::seed_from_u64(state)
+    }
+}
+
+impl Default for WorldSeed {
+    fn default() -> Self {
+        Self::random()
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::{Chunk, Level, TerrainChunk, Vertex};
+
+// Coarsens the terrain grid with distance from the cheese.
+// Chunks keep the level's footprint, so a larger quad size means fewer vertices per chunk.
+#[derive(Clone, Copy, Debug)]
+#[derive(Resource)]
+pub struct AdaptiveQuadSize {
+    // the quad size at the cheese
+    pub near_size: Vec2,
+    // the quad size at and beyond blend_distance
+    pub far_size: Vec2,
+    pub blend_distance: f32,
+}
+
+impl AdaptiveQuadSize {
+    // the number of steps between near_size and far_size
+    // chunks are only regenerated when they cross into another band
+    const BANDS: u32 = 4;
+
+    pub fn new(near_size: Vec2, far_size: Vec2, blend_distance: f32) -> Self {
+        Self {
+            near_size,
+            far_size,
+            blend_distance,
+        }
+    }
+
+    pub fn band(&self, distance: f32) -> u32 {
+        let ratio = (distance / self.blend_distance).clamp(0., 1.);
+        (ratio * Self::BANDS as f32).floor() as u32
+    }
+
+    pub fn quad_size(&self, band: u32) -> Vec2 {
+        self.near_size
+            .lerp(self.far_size, band as f32 / Self::BANDS as f32)
+    }
+
+    // the band of the level chunk at origin, measured from the level's focus on the XZ plane
+    pub fn band_for_chunk(&self, origin: Vertex, level: &Level) -> u32 {
+        let chunk = Chunk::new(origin, level.chunk_size, level.quad_size);
+        let center = TerrainChunk::new(chunk).compute_centroid();
+        let distance = Vec2::new(center.x - level.focus.x, center.z - level.focus.z).length();
+        self.band(distance)
+    }
+
+    // builds a chunk with the level's footprint using the quad size of the given band
+    pub fn chunk(&self, origin: Vertex, band: u32, level: &Level) -> Chunk {
+        let extent =
+            level.quad_size * Vec2::new(level.chunk_size.x as f32, level.chunk_size.z as f32);
+        let target_quad_size = self.quad_size(band);
+        // round to a whole number of quads so that neighboring chunks still line up
+        let size = Vertex::new(
+            (extent.x / target_quad_size.x).round().max(1.) as i32,
+            (extent.y / target_quad_size.y).round().max(1.) as i32,
+        );
+        let quad_size = extent / Vec2::new(size.x as f32, size.z as f32);
+        Chunk::new(origin, size, quad_size)
+    }
+}
+
+impl Default for AdaptiveQuadSize {
+    fn default() -> Self {
+        Self::new(Vec2::ONE * 2., Vec2::ONE * 8., 300.)
+    }
+}
@@ -1,23 +1,154 @@
+use derive_more::Display;
 use noise::NoiseFn;
 
 use bevy::{
     prelude::*,
-    render::{mesh::Indices, render_resource::PrimitiveTopology},
+    render::{
+        mesh::{Indices, MeshVertexAttribute, VertexAttributeValues},
+        render_resource::{PrimitiveTopology, VertexFormat},
+    },
 };
 use bevy_xpbd_3d::prelude::*;
 
-use crate::{Chunk, TextureAssets, Vertex};
+use crate::{Chunk, TerrainColorPalette, TerrainNoise, TextureAssets, Vertex, WorldEdges};
 
-#[derive(Debug, Clone, Default)]
+// additional per-vertex data to insert into a chunk mesh, in the same order as `Chunk::iter_by_row`
+#[derive(Debug, Clone)]
+pub struct CustomVertexAttribute {
+    pub attribute: MeshVertexAttribute,
+    pub values: VertexAttributeValues,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttributeMismatchError {
+    // the attribute does not have one value per chunk vertex
+    Count {
+        attribute: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    // the values do not match the attribute's declared vertex format
+    Format {
+        attribute: &'static str,
+        expected: VertexFormat,
+        found: VertexFormat,
+    },
+    // the attribute is one the chunk mesh already generates
+    Reserved {
+        attribute: &'static str,
+    },
+}
+
+impl std::fmt::Display for AttributeMismatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Count {
+                attribute,
+                expected,
+                found,
+            } => write!(
+                f,
+                "attribute {} has {} values but the chunk has {} vertices",
+                attribute, found, expected,
+            ),
+            Self::Format {
+                attribute,
+                expected,
+                found,
+            } => write!(
+                f,
+                "attribute {} expects values of format {:?} but was given {:?}",
+                attribute, expected, found,
+            ),
+            Self::Reserved { attribute } => write!(
+                f,
+                "attribute {} is generated for every chunk and cannot be replaced",
+                attribute,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AttributeMismatchError {}
+
+// the texture tiling applied to a chunk's mesh on top of the tiling baked in when it was generated
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[derive(Component)]
+pub struct TerrainChunkUvScale(pub Vec2);
+
+impl Default for TerrainChunkUvScale {
+    fn default() -> Self {
+        Self(Vec2::ONE)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[derive(Component, Display, Reflect)]
+#[display(fmt = "terrain {}", chunk)]
+#[reflect(Component)]
 pub struct TerrainChunk {
     // the chunk being rendered
     pub chunk: Chunk,
+    // the bounds of the world, if any, used to remap noise coordinates past the edges
+    pub edges: Option<WorldEdges>,
+    // scales the chunk's grid onto the noise grid, for chunks coarser than the level's quad size
+    pub noise_scale: Vec2,
+    // the seed of a neighboring biome and how strongly to fade into its noise towards the edges
+    pub blend_seeds: Option<(u32, f32)>,
+}
+
+impl Default for TerrainChunk {
+    fn default() -> Self {
+        Self::new(Chunk::default())
+    }
 }
 
 impl TerrainChunk {
     pub fn new(chunk: Chunk) -> Self {
-        Self { chunk }
+        Self {
+            chunk,
+            edges: None,
+            noise_scale: Vec2::ONE,
+            blend_seeds: None,
+        }
+    }
+
+    pub fn with_noise_scale(mut self, noise_scale: Vec2) -> Self {
+        self.noise_scale = noise_scale;
+        self
+    }
+
+    pub fn with_edges(mut self, edges: WorldEdges) -> Self {
+        self.edges = Some(edges);
+        self
+    }
+
+    pub fn with_blend_seeds(mut self, neighbor_seed: u32, blend_weight: f32) -> Self {
+        self.blend_seeds = Some((neighbor_seed, blend_weight));
+        self
+    }
+
+    // panics if the chunk cannot produce a valid mesh, meant to be called in debug builds
+    pub fn debug_assert_valid(&self) {
+        assert!(
+            self.chunk.size.x >= 1 && self.chunk.size.z >= 1,
+            "terrain chunk {} needs at least one quad along each side to form triangles, \
+            but has size {}",
+            self.chunk.origin,
+            self.chunk.size,
+        );
+        assert!(
+            self.chunk.quad_size.x > 0. && self.chunk.quad_size.y > 0.,
+            "terrain chunk {} needs a positive quad size, but has {}",
+            self.chunk.origin,
+            self.chunk.quad_size,
+        );
+        assert!(
+            self.noise_scale.x > 0. && self.noise_scale.y > 0.,
+            "terrain chunk {} needs a positive noise scale, but has {}",
+            self.chunk.origin,
+            self.noise_scale,
+        );
     }
 
     // get the triangles to render the quad with origin at local_vertex
@@ -36,97 +167,545 @@ impl TerrainChunk {
         ]
     }
 
+    // samples the noise at a local vertex, respecting the world edges
+    pub fn sample_noise(&self, local_vertex: Vertex, noise: &impl NoiseFn<f64, 2>) -> f32 {
+        let global_vertex = self.chunk.to_global_coords(local_vertex);
+        let noise_vertex = self
+            .edges
+            .map_or(global_vertex, |edges| edges.apply(global_vertex));
+        noise.get([
+            noise_vertex.x as f64 * self.noise_scale.x as f64,
+            noise_vertex.z as f64 * self.noise_scale.y as f64,
+        ]) as f32
+    }
+
+    // how far a local vertex is from the center of the chunk, from 0 at the center to 1 on the edges
+    pub fn edge_distance_factor(&self, local_vertex: Vertex) -> f32 {
+        let x = (local_vertex.x as f32 / self.chunk.size.x as f32 * 2. - 1.).abs();
+        let z = (local_vertex.z as f32 / self.chunk.size.z as f32 * 2. - 1.).abs();
+        x.max(z)
+    }
+
+    // samples the noise at a local vertex, fading into a neighboring biome's noise near the edges
+    pub fn sample_blended_noise(
+        &self,
+        local_vertex: Vertex,
+        noise: &impl NoiseFn<f64, 2>,
+        neighbor_noise: &impl NoiseFn<f64, 2>,
+        blend_weight: f32,
+    ) -> f32 {
+        let sample = self.sample_noise(local_vertex, noise);
+        let neighbor_sample = self.sample_noise(local_vertex, neighbor_noise);
+        let t = (blend_weight * self.edge_distance_factor(local_vertex)).clamp(0., 1.);
+        sample + (neighbor_sample - sample) * t
+    }
+
+    // the gradient of the rendered height above the slope at a local vertex, in height per world
+    // unit along x and z, using central differences inside the chunk and one-sided differences on
+    // its borders
+    //
+    // this follows `compute_vertex_position`, so the flat hilltop has no slope and the chunks
+    // at the start line ramp their slope in alongside the noise
+    pub fn compute_slope_at(&self, local_vertex: Vertex, noise: &impl NoiseFn<f64, 2>) -> Vec2 {
+        let x_low = (local_vertex.x - 1).max(0);
+        let x_high = (local_vertex.x + 1).min(self.chunk.size.x);
+        let z_low = (local_vertex.z - 1).max(0);
+        let z_high = (local_vertex.z + 1).min(self.chunk.size.z);
+        let blend_noise = self.blend_noise();
+        let height = |x: i32, z: i32| {
+            self.compute_vertex_position_with(Vertex::new(x, z), noise, blend_noise.as_ref())
+                .1
+        };
+
+        let dh_dx = (height(x_high, local_vertex.z) - height(x_low, local_vertex.z))
+            / ((x_high - x_low) as f32 * self.chunk.quad_size.x);
+        let dh_dz = (height(local_vertex.x, z_high) - height(local_vertex.x, z_low))
+            / ((z_high - z_low) as f32 * self.chunk.quad_size.y);
+        Vec2::new(dh_dx, dh_dz)
+    }
+
     pub fn generate_mesh(&self, noise: &impl NoiseFn<f64, 2>) -> Mesh {
+        self.build_mesh(noise, None)
+    }
+
+    // generates the mesh with an additional vertex color attribute sampled from the palette
+    pub fn generate_colored_mesh(
+        &self,
+        noise: &impl NoiseFn<f64, 2>,
+        palette: &TerrainColorPalette,
+    ) -> Mesh {
+        self.build_mesh(noise, Some(palette))
+    }
+
+    // gets the mesh-space position of a local vertex, along with its height above the slope
+    pub fn compute_vertex_position(
+        &self,
+        local_vertex: Vertex,
+        noise: &impl NoiseFn<f64, 2>,
+    ) -> (Vec3, f32) {
+        self.compute_vertex_position_with(local_vertex, noise, self.blend_noise().as_ref())
+    }
+
+    // the neighboring biome's noise and blend weight, built once so that callers sampling many
+    // vertices can share it
+    pub(crate) fn blend_noise(&self) -> Option<(TerrainNoise, f32)> {
+        self.blend_seeds
+            .map(|(seed, weight)| (TerrainNoise::new(seed), weight))
+    }
+
+    // samples the noise the mesh is built from, fading into the neighboring biome if there is one
+    pub(crate) fn sample_terrain_noise(
+        &self,
+        local_vertex: Vertex,
+        noise: &impl NoiseFn<f64, 2>,
+        blend_noise: Option<&(TerrainNoise, f32)>,
+    ) -> f32 {
+        // the hilltop is flat, so its noise is never sampled
+        if self.chunk.origin.z > 0 {
+            return 0.;
+        }
+        match blend_noise {
+            Some((blend_noise, weight)) => {
+                self.sample_blended_noise(local_vertex, noise, &blend_noise.get(), *weight)
+            }
+            None => self.sample_noise(local_vertex, noise),
+        }
+    }
+
+    pub(crate) fn compute_vertex_position_with(
+        &self,
+        local_vertex: Vertex,
+        noise: &impl NoiseFn<f64, 2>,
+        blend_noise: Option<&(TerrainNoise, f32)>,
+    ) -> (Vec3, f32) {
+        let noise_sample = self.sample_terrain_noise(local_vertex, noise, blend_noise);
+        self.position_from_sample(local_vertex, noise_sample)
+    }
+
+    fn position_from_sample(&self, local_vertex: Vertex, noise_sample: f32) -> (Vec3, f32) {
+        let position = self.chunk.to_translation(local_vertex);
+        let unsloped_position = Vec3::new(position.x, 0., position.y);
+        if self.chunk.origin.z > 0 {
+            return (unsloped_position, 0.);
+        }
+
+        let slope = Quat::from_rotation_x(std::f32::consts::FRAC_PI_4);
+        let sloped_noise = slope * Vec3::new(0., noise_sample, 0.);
+        let sloped_position = Vec3::new(position.x, -position.y, position.y);
+        let target_position = sloped_position + sloped_noise;
+
+        if self.chunk.origin.z < 0 {
+            (target_position, noise_sample)
+        } else {
+            // blend between 0 and the noise
+            let chunk_z_ratio =
+                (self.chunk.size.z as f32 - local_vertex.z as f32) / self.chunk.size.z as f32;
+            (
+                target_position.lerp(unsloped_position, chunk_z_ratio),
+                noise_sample * (1. - chunk_z_ratio),
+            )
+        }
+    }
+
+    fn build_mesh(
+        &self,
+        noise: &impl NoiseFn<f64, 2>,
+        palette: Option<&TerrainColorPalette>,
+    ) -> Mesh {
+        #[cfg(debug_assertions)]
+        self.debug_assert_valid();
+
         let num_vertices = self.chunk.count_vertices() as usize;
+        // chunks on the hilltop behind the start are always flat, so the noise is never sampled
+        if self.chunk.origin.z > 0 {
+            let mesh = self.generate_flat_mesh();
+            return match palette {
+                Some(palette) => mesh.with_inserted_attribute(
+                    Mesh::ATTRIBUTE_COLOR,
+                    vec![palette.sample(0.).as_linear_rgba_f32(); num_vertices],
+                ),
+                None => mesh,
+            };
+        }
+
         let num_indices = self.chunk.count_indices() as usize;
         let mut positions: Vec<[f32; 3]> = Vec::with_capacity(num_vertices);
         let mut normals: Vec<[f32; 3]> = Vec::with_capacity(num_vertices);
         let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(num_vertices);
+        let mut colors: Vec<[f32; 4]> =
+            Vec::with_capacity(if palette.is_some() { num_vertices } else { 0 });
         // Each row is (M - 1) X (N-1) quads
         let mut indices: Vec<u32> = Vec::with_capacity(num_indices);
 
-        let slope = Quat::from_rotation_x(std::f32::consts::FRAC_PI_4);
+        let blend_noise = self.blend_noise();
+
+        #[cfg(feature = "terrain_profiling")]
+        let mut noise_nanos: Vec<f32> = Vec::with_capacity(num_vertices);
 
         for vertex in self.chunk.iter_by_row() {
             let global_vertex = self.chunk.to_global_coords(vertex);
-            let noise_sample = noise.get([global_vertex.x as f64, global_vertex.z as f64]) as f32;
-            let sloped_noise = slope * Vec3::new(0., noise_sample, 0.);
-            let position = self.chunk.to_translation(vertex);
-            let sloped_position = Vec3::new(position.x, -position.y, position.y);
-            let unsloped_position = Vec3::new(position.x, 0., position.y);
-            let target_position = sloped_position + sloped_noise;
-
-            match self.chunk.origin.z.cmp(&0) {
-                std::cmp::Ordering::Less => {
-                    positions.push(target_position.to_array());
-                    normals.push(Vec3::Y.to_array());
-                }
-                std::cmp::Ordering::Equal => {
-                    // blend between 0 and the noise
-                    let chunk_z_ratio =
-                        (self.chunk.size.z as f32 - vertex.z as f32) / self.chunk.size.z as f32;
-
-                    positions.push(
-                        target_position
-                            .lerp(unsloped_position, chunk_z_ratio)
-                            .to_array(),
-                    );
-                    normals.push(Vec3::Y.to_array());
-                }
-                std::cmp::Ordering::Greater => {
-                    positions.push(unsloped_position.to_array());
-                    normals.push(Vec3::Y.to_array());
-                }
+            #[cfg(feature = "terrain_profiling")]
+            let sample_start = bevy::utils::Instant::now();
+            let noise_sample = self.sample_terrain_noise(vertex, noise, blend_noise.as_ref());
+            #[cfg(feature = "terrain_profiling")]
+            noise_nanos.push(sample_start.elapsed().as_secs_f32() * 1e9);
+            let (position, height) = self.position_from_sample(vertex, noise_sample);
+            positions.push(position.to_array());
+            normals.push(Vec3::Y.to_array());
+
+            if let Some(palette) = palette {
+                colors.push(palette.sample(height).as_linear_rgba_f32());
             }
 
-            uvs.push([global_vertex.z as f32 / 8., global_vertex.x as f32 / 8.]);
+            uvs.push([
+                global_vertex.z as f32 * self.noise_scale.y / 8.,
+                global_vertex.x as f32 * self.noise_scale.x / 8.,
+            ]);
 
             if vertex.x < self.chunk.size.x && vertex.z < self.chunk.size.z {
                 indices.extend_from_slice(&self.get_quad_triangles(vertex));
             }
         }
 
-        Mesh::new(PrimitiveTopology::TriangleList)
+        let mesh = Mesh::new(PrimitiveTopology::TriangleList)
             .with_indices(Some(Indices::U32(indices)))
             .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
             .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        #[cfg(feature = "terrain_profiling")]
+        let mesh = mesh
+            .with_inserted_attribute(crate::VertexTimingMap::ATTRIBUTE_NOISE_NANOS, noise_nanos);
+        if palette.is_some() {
+            mesh.with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+        } else {
+            mesh
+        }
+    }
+
+    // generates an unsloped grid with Y = 0, as used by the hilltop chunks behind the start
+    pub fn generate_flat_mesh(&self) -> Mesh {
+        let num_vertices = self.chunk.count_vertices() as usize;
+        let num_indices = self.chunk.count_indices() as usize;
+        let mut positions: Vec<[f32; 3]> = Vec::with_capacity(num_vertices);
+        let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(num_vertices);
+        let mut indices: Vec<u32> = Vec::with_capacity(num_indices);
+
+        for vertex in self.chunk.iter_by_row() {
+            let global_vertex = self.chunk.to_global_coords(vertex);
+            let position = self.chunk.to_translation(vertex);
+            positions.push([position.x, 0., position.y]);
+            uvs.push([
+                global_vertex.z as f32 * self.noise_scale.y / 8.,
+                global_vertex.x as f32 * self.noise_scale.x / 8.,
+            ]);
+
+            if vertex.x < self.chunk.size.x && vertex.z < self.chunk.size.z {
+                indices.extend_from_slice(&self.get_quad_triangles(vertex));
+            }
+        }
+
+        Mesh::new(PrimitiveTopology::TriangleList)
+            .with_indices(Some(Indices::U32(indices)))
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0., 1., 0.]; num_vertices])
             .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
     }
 
+    // the world-space translation of the chunk's local origin
+    pub fn compute_chunk_offset(&self) -> Vec3 {
+        let x = self.chunk.origin.x as f32 * self.chunk.size.x as f32 * self.chunk.quad_size.x;
+        let y = (self.chunk.origin.z as f32).clamp(std::f32::NEG_INFINITY, 0.)
+            * self.chunk.size.z as f32
+            * self.chunk.quad_size.y;
+        let z = -(self.chunk.origin.z as f32 * self.chunk.size.z as f32) * self.chunk.quad_size.y;
+        Vec3::new(x, y, z)
+    }
+
+    // the world-space center of the chunk's footprint, at the height of its offset
+    pub fn compute_centroid(&self) -> Vec3 {
+        self.compute_chunk_offset()
+            + Vec3::new(
+                self.chunk.size.x as f32 * self.chunk.quad_size.x / 2.,
+                0.,
+                self.chunk.size.z as f32 * self.chunk.quad_size.y / 2.,
+            )
+    }
+
+    // converts a position in the chunk's mesh space (e.g. a mesh vertex position) to world space
+    pub fn local_to_world(&self, local: Vec3) -> Vec3 {
+        self.compute_chunk_offset() + local
+    }
+
+    // gets the origin (in chunk units) of the chunk whose footprint contains world_pos,
+    // inverting the math in `compute_chunk_offset`
+    pub fn world_to_chunk_coord(world_pos: Vec3, chunk_size: Vertex, quad_size: Vec2) -> Vertex {
+        Chunk::origin_containing(world_pos, chunk_size, quad_size)
+    }
+
+    pub fn generate_mesh_with_attributes(
+        &self,
+        noise: &impl NoiseFn<f64, 2>,
+        extra: &[CustomVertexAttribute],
+    ) -> Result<Mesh, AttributeMismatchError> {
+        let num_vertices = self.chunk.count_vertices() as usize;
+        let reserved = [
+            Mesh::ATTRIBUTE_POSITION.id,
+            Mesh::ATTRIBUTE_NORMAL.id,
+            Mesh::ATTRIBUTE_UV_0.id,
+        ];
+        for custom in extra {
+            let attribute = custom.attribute.name;
+            if reserved.contains(&custom.attribute.id) {
+                return Err(AttributeMismatchError::Reserved { attribute });
+            }
+            // `Mesh::insert_attribute` panics on a format mismatch, so check it up front
+            let found = VertexFormat::from(&custom.values);
+            if found != custom.attribute.format {
+                return Err(AttributeMismatchError::Format {
+                    attribute,
+                    expected: custom.attribute.format,
+                    found,
+                });
+            }
+            if custom.values.len() != num_vertices {
+                return Err(AttributeMismatchError::Count {
+                    attribute,
+                    expected: num_vertices,
+                    found: custom.values.len(),
+                });
+            }
+        }
+
+        let mut mesh = self.generate_mesh(noise);
+        for custom in extra {
+            mesh.insert_attribute(custom.attribute, custom.values.clone());
+        }
+        Ok(mesh)
+    }
+
+    // retiles the mesh's texture coordinates in place, without regenerating the mesh
+    // a scale with a non-positive component can't be undone later, so it is ignored
+    pub fn rescale_uvs(
+        mesh: &mut Mesh,
+        uv_scale: &mut TerrainChunkUvScale,
+        new_scale: Vec2,
+    ) -> bool {
+        if !new_scale.cmpgt(Vec2::ZERO).all() || !uv_scale.0.cmpgt(Vec2::ZERO).all() {
+            return false;
+        }
+        let ratio = new_scale / uv_scale.0;
+        if let Some(VertexAttributeValues::Float32x2(uvs)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0)
+        {
+            for uv in uvs.iter_mut() {
+                *uv = (Vec2::from(*uv) * ratio).to_array();
+            }
+        }
+        uv_scale.0 = new_scale;
+        true
+    }
+
     pub fn to_bundle(
         self,
         noise: &impl NoiseFn<f64, 2>,
         textures: &TextureAssets,
+        palette: Option<&TerrainColorPalette>,
         meshes: &mut Assets<Mesh>,
         materials: &mut Assets<StandardMaterial>,
     ) -> impl Bundle {
-        let mesh = self.generate_mesh(noise);
-        let x = self.chunk.origin.x as f32 * self.chunk.size.x as f32 * self.chunk.quad_size.x;
-        let y = (self.chunk.origin.z as f32).clamp(std::f32::NEG_INFINITY, 0.)
-            * self.chunk.size.z as f32
-            * self.chunk.quad_size.y;
-        let z = -(self.chunk.origin.z as f32 * self.chunk.size.z as f32) * self.chunk.quad_size.y;
+        let mesh = match palette {
+            Some(palette) => self.generate_colored_mesh(noise, palette),
+            None => self.generate_mesh(noise),
+        };
+        self.to_bundle_with_mesh(meshes.add(mesh), textures, palette, materials)
+    }
+
+    // builds the bundle around a mesh that was already generated, e.g. by a preloading task
+    pub fn to_bundle_with_mesh(
+        self,
+        mesh: Handle<Mesh>,
+        textures: &TextureAssets,
+        palette: Option<&TerrainColorPalette>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> impl Bundle {
+        (
+            RigidBody::Static,
+            ColliderDensity(1e7),
+            AsyncCollider(ComputedCollider::TriMesh),
+            self.to_visual_bundle(mesh, textures, palette, materials),
+        )
+    }
+
+    // builds the rendered part of the chunk without any physics components,
+    // e.g. for previewing terrain in tools that do not run the physics plugins
+    pub fn to_visual_bundle(
+        self,
+        mesh: Handle<Mesh>,
+        textures: &TextureAssets,
+        palette: Option<&TerrainColorPalette>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> impl Bundle {
+        // with a palette, the terrain is colored by its vertices and no textures are sampled
+        let material = if palette.is_some() {
+            StandardMaterial {
+                base_color: Color::WHITE,
+                perceptual_roughness: 0.9,
+                ..Default::default()
+            }
+        } else {
+            StandardMaterial {
+                base_color_texture: Some(textures.ground.clone()),
+                normal_map_texture: Some(textures.ground_normal.clone()),
+                thickness_texture: Some(textures.ground_displacement.clone()),
+                depth_map: Some(textures.ground_displacement.clone()),
+                ..Default::default()
+            }
+        };
+        let offset = self.compute_chunk_offset();
         (
             Name::new(format!(
                 "Terrain Chunk {}x{}",
                 self.chunk.origin.x, self.chunk.origin.z,
             )),
-            RigidBody::Static,
-            ColliderDensity(1e7),
-            AsyncCollider(ComputedCollider::TriMesh),
+            TerrainChunkUvScale::default(),
             PbrBundle {
-                mesh: meshes.add(mesh),
-                material: materials.add(StandardMaterial {
-                    base_color_texture: Some(textures.ground.clone()),
-                    normal_map_texture: Some(textures.ground_normal.clone()),
-                    thickness_texture: Some(textures.ground_displacement.clone()),
-                    depth_map: Some(textures.ground_displacement.clone()),
-                    ..Default::default()
-                }),
-                transform: Transform::from_xyz(x, y, z),
+                mesh,
+                material: materials.add(material),
+                transform: Transform::from_translation(offset),
                 ..Default::default()
             },
             self,
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use noise::{Perlin, Simplex};
+
+    use super::*;
+
+    #[test]
+    fn centroid_is_equidistant_from_footprint_corners() {
+        let chunk = Chunk::new((3, -2).into(), (10, 6).into(), Vec2::new(2., 3.));
+        let terrain_chunk = TerrainChunk::new(chunk.clone());
+        let offset = terrain_chunk.compute_chunk_offset();
+        let extent = Vec3::new(
+            chunk.size.x as f32 * chunk.quad_size.x,
+            0.,
+            chunk.size.z as f32 * chunk.quad_size.y,
+        );
+        let corners = [
+            offset,
+            offset + Vec3::new(extent.x, 0., 0.),
+            offset + Vec3::new(0., 0., extent.z),
+            offset + extent,
+        ];
+
+        let centroid = terrain_chunk.compute_centroid();
+        let expected = centroid.distance(corners[0]);
+        for corner in corners {
+            assert!((centroid.distance(corner) - expected).abs() < 1e-4);
+        }
+    }
+
+    // the noise benchmarks are only meaningful if the same seed gives the same terrain
+    #[test]
+    fn generated_meshes_are_deterministic() {
+        let chunk = TerrainChunk::new(Chunk::new((0, -1).into(), (50, 50).into(), Vec2::ONE * 2.));
+        let positions = |mesh: Mesh| {
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                .and_then(|positions| positions.as_float3())
+                .map(<[[f32; 3]]>::to_vec)
+        };
+        assert_eq!(
+            positions(chunk.generate_mesh(&Perlin::new(54321))),
+            positions(chunk.generate_mesh(&Perlin::new(54321))),
+        );
+        assert_eq!(
+            positions(chunk.generate_mesh(&Simplex::new(54321))),
+            positions(chunk.generate_mesh(&Simplex::new(54321))),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one quad")]
+    fn debug_assert_valid_rejects_zero_size() {
+        let chunk = Chunk::new((0, 0).into(), (0, 4).into(), Vec2::ONE);
+        TerrainChunk::new(chunk).debug_assert_valid();
+    }
+
+    #[test]
+    #[should_panic(expected = "positive quad size")]
+    fn debug_assert_valid_rejects_non_positive_quad_size() {
+        let chunk = Chunk::new((0, 0).into(), (4, 4).into(), Vec2::new(1., 0.));
+        TerrainChunk::new(chunk).debug_assert_valid();
+    }
+
+    #[test]
+    #[should_panic(expected = "positive noise scale")]
+    fn debug_assert_valid_rejects_non_positive_noise_scale() {
+        TerrainChunk::default()
+            .with_noise_scale(Vec2::new(-1., 1.))
+            .debug_assert_valid();
+    }
+
+    #[test]
+    fn rescale_uvs_ignores_non_positive_scales() {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[1., 2.], [3., 4.]]);
+        let mut uv_scale = TerrainChunkUvScale::default();
+
+        assert!(!TerrainChunk::rescale_uvs(
+            &mut mesh,
+            &mut uv_scale,
+            Vec2::new(0., 2.)
+        ));
+        assert!(!TerrainChunk::rescale_uvs(
+            &mut mesh,
+            &mut uv_scale,
+            Vec2::new(2., -1.)
+        ));
+        assert_eq!(uv_scale, TerrainChunkUvScale::default());
+
+        assert!(TerrainChunk::rescale_uvs(
+            &mut mesh,
+            &mut uv_scale,
+            Vec2::new(2., 0.5)
+        ));
+        assert_eq!(uv_scale.0, Vec2::new(2., 0.5));
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("the mesh lost its uvs");
+        };
+        assert_eq!(uvs, &vec![[2., 1.], [6., 2.]]);
+    }
+
+    #[test]
+    fn translation_maps_to_the_chunk_containing_it() {
+        let chunk_size = Vertex::new(10, 6);
+        let quad_size = Vec2::new(2., 3.);
+        let chunk_length = Vec2::new(20., 18.);
+        let translations = [
+            Vec3::new(0., 0., 0.),
+            Vec3::new(5., 1., 7.),
+            Vec3::new(-0.5, -2., -0.5),
+            Vec3::new(-20., 0., -18.),
+            Vec3::new(-41., 3., 37.),
+            Vec3::new(63., 0., -55.),
+        ];
+        for translation in translations {
+            let chunk = Chunk::from_translation(translation, chunk_size, quad_size);
+            assert_eq!(
+                chunk.origin,
+                TerrainChunk::world_to_chunk_coord(translation, chunk_size, quad_size),
+            );
+            let offset = TerrainChunk::new(chunk).compute_chunk_offset();
+            assert!(
+                (offset.x..offset.x + chunk_length.x).contains(&translation.x)
+                    && (offset.z..offset.z + chunk_length.y).contains(&translation.z),
+                "{translation} is outside the chunk at {offset}",
+            );
+        }
+    }
+}
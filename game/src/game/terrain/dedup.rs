@@ -0,0 +1,127 @@
+use bevy::{
+    prelude::*,
+    render::mesh::{Indices, VertexAttributeValues},
+    utils::HashMap,
+};
+
+use crate::TerrainChunk;
+
+impl TerrainChunk {
+    // merges identical vertices of an un-indexed mesh and indexes it, leaving indexed meshes and
+    // meshes with attribute formats other than f32 untouched
+    //
+    // the meshes generated for chunks are already indexed, so this guards any code path that
+    // produces a triangle soup instead
+    pub fn deduplicate_vertices(mesh: &mut Mesh) {
+        if mesh.indices().is_some() {
+            return;
+        }
+        let num_vertices = mesh.count_vertices();
+        let mut keys: Vec<Vec<u32>> = vec![Vec::new(); num_vertices];
+        for (_, values) in mesh.attributes() {
+            let Some(components) = float_components(values) else {
+                return;
+            };
+            for (key, component) in keys.iter_mut().zip(components) {
+                key.extend(component.iter().map(|value| value.to_bits()));
+            }
+        }
+
+        let mut unique_vertices: Vec<usize> = Vec::new();
+        let mut unique_indices: HashMap<Vec<u32>, u32> = HashMap::new();
+        let indices = keys
+            .into_iter()
+            .enumerate()
+            .map(|(vertex, key)| {
+                *unique_indices.entry(key).or_insert_with(|| {
+                    unique_vertices.push(vertex);
+                    unique_vertices.len() as u32 - 1
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for (_, values) in mesh.attributes_mut() {
+            *values = select_vertices(values, &unique_vertices);
+        }
+        mesh.set_indices(Some(Indices::U32(indices)));
+    }
+}
+
+fn float_components(values: &VertexAttributeValues) -> Option<Vec<&[f32]>> {
+    match values {
+        VertexAttributeValues::Float32(values) => {
+            Some(values.iter().map(std::slice::from_ref).collect())
+        }
+        VertexAttributeValues::Float32x2(values) => {
+            Some(values.iter().map(|value| value.as_slice()).collect())
+        }
+        VertexAttributeValues::Float32x3(values) => {
+            Some(values.iter().map(|value| value.as_slice()).collect())
+        }
+        VertexAttributeValues::Float32x4(values) => {
+            Some(values.iter().map(|value| value.as_slice()).collect())
+        }
+        _ => None,
+    }
+}
+
+fn select_vertices(values: &VertexAttributeValues, vertices: &[usize]) -> VertexAttributeValues {
+    match values {
+        VertexAttributeValues::Float32(values) => {
+            VertexAttributeValues::Float32(vertices.iter().map(|index| values[*index]).collect())
+        }
+        VertexAttributeValues::Float32x2(values) => {
+            VertexAttributeValues::Float32x2(vertices.iter().map(|index| values[*index]).collect())
+        }
+        VertexAttributeValues::Float32x3(values) => {
+            VertexAttributeValues::Float32x3(vertices.iter().map(|index| values[*index]).collect())
+        }
+        VertexAttributeValues::Float32x4(values) => {
+            VertexAttributeValues::Float32x4(vertices.iter().map(|index| values[*index]).collect())
+        }
+        // only reachable for formats that were already rejected
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::render_resource::PrimitiveTopology;
+    use noise::Perlin;
+
+    use crate::Chunk;
+
+    use super::*;
+
+    #[test]
+    fn generated_meshes_are_indexed() {
+        let noise = Perlin::new(1);
+        // hilltop, transition, and downhill chunks are each built differently
+        for origin_z in [1, 0, -1] {
+            let chunk = Chunk::new((0, origin_z).into(), (8, 8).into(), Vec2::ONE);
+            let mesh = TerrainChunk::new(chunk).generate_mesh(&noise);
+            assert!(mesh.indices().is_some());
+        }
+    }
+
+    #[test]
+    fn deduplicate_vertices_merges_shared_corners() {
+        // a quad as two triangles that share an edge, without indices
+        let positions: Vec<[f32; 3]> = vec![
+            [0., 0., 0.],
+            [1., 0., 0.],
+            [0., 0., 1.],
+            [1., 0., 0.],
+            [1., 0., 1.],
+            [0., 0., 1.],
+        ];
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0., 1., 0.]; 6]);
+
+        TerrainChunk::deduplicate_vertices(&mut mesh);
+
+        assert_eq!(mesh.count_vertices(), 4);
+        assert_eq!(mesh.indices().map(Indices::len), Some(6));
+    }
+}
@@ -0,0 +1,90 @@
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+
+use crate::TerrainChunk;
+
+// Tuning for how hard impacts press dents into the terrain
+#[derive(Clone, Copy, Debug)]
+#[derive(Resource, Reflect)]
+pub struct TerrainDeformation {
+    // bodies lighter than this (in kg) never dent the terrain
+    pub min_mass: f32,
+    // the smallest change in momentum that leaves a dent
+    pub impulse_threshold: f32,
+    // how deep the dent is per unit of impulse past the threshold
+    pub depth_per_impulse: f32,
+    pub max_depth: f32,
+    // the standard deviation of the dent's gaussian profile
+    pub radius: f32,
+}
+
+impl Default for TerrainDeformation {
+    fn default() -> Self {
+        Self {
+            min_mass: 10.,
+            impulse_threshold: 2000.,
+            depth_per_impulse: 0.0002,
+            max_depth: 0.8,
+            radius: 1.5,
+        }
+    }
+}
+
+// A gaussian dent pressed into the terrain, kept so it can be pressed into the chunks again
+// whenever they are regenerated
+#[derive(Clone, Copy, Debug)]
+pub struct TerrainDent {
+    pub center: Vec3,
+    pub depth: f32,
+    // the standard deviation of the dent's gaussian profile
+    pub radius: f32,
+}
+
+// The velocity of a body before the latest physics step, used to measure impacts
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component)]
+pub struct ImpactVelocity(pub Vec3);
+
+impl TerrainChunk {
+    // past three standard deviations a dent is too shallow to matter
+    fn dent_reach(radius: f32) -> f32 {
+        radius * 3.
+    }
+
+    // whether a dent reaches into this chunk's footprint
+    pub fn is_in_dent_reach(&self, dent: &TerrainDent) -> bool {
+        let reach = Self::dent_reach(dent.radius);
+        let center = dent.center - self.compute_chunk_offset();
+        let extent = Vec2::new(
+            self.chunk.size.x as f32 * self.chunk.quad_size.x,
+            self.chunk.size.z as f32 * self.chunk.quad_size.y,
+        );
+        center.x >= -reach
+            && center.z >= -reach
+            && center.x <= extent.x + reach
+            && center.z <= extent.y + reach
+    }
+
+    // presses the dent into the mesh, moving only the vertices in its reach, and returns whether
+    // any vertices were moved
+    pub fn apply_dent(&self, mesh: &mut Mesh, dent: &TerrainDent) -> bool {
+        let reach = Self::dent_reach(dent.radius);
+        let center = dent.center - self.compute_chunk_offset();
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+        else {
+            return false;
+        };
+        let mut dented = false;
+        for position in positions.iter_mut() {
+            let distance_squared =
+                Vec2::new(position[0] - center.x, position[2] - center.z).length_squared();
+            if distance_squared > reach * reach {
+                continue;
+            }
+            position[1] -=
+                dent.depth * (-distance_squared / (2. * dent.radius * dent.radius)).exp();
+            dented = true;
+        }
+        dented
+    }
+}
@@ -0,0 +1,64 @@
+use derive_more::Display;
+
+use bevy::prelude::*;
+
+use crate::Vertex;
+
+// How noise coordinates are remapped once they pass the edge of a bounded world
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Display, Reflect)]
+pub enum EdgeBehavior {
+    // repeat the edge sample, producing flat terrain beyond the edge
+    #[default]
+    Clamp,
+    // reflect the terrain back across the edge
+    Mirror,
+    // continue from the opposite edge, as on a torus
+    Wrap,
+}
+
+impl EdgeBehavior {
+    // maps a coordinate into the inclusive range [min, max]
+    pub fn apply(self, value: i32, min: i32, max: i32) -> i32 {
+        let span = max - min;
+        if span <= 0 {
+            return min;
+        }
+        match self {
+            EdgeBehavior::Clamp => value.clamp(min, max),
+            EdgeBehavior::Mirror => {
+                let period = span * 2;
+                let offset = (value - min).rem_euclid(period);
+                min + if offset > span {
+                    period - offset
+                } else {
+                    offset
+                }
+            }
+            EdgeBehavior::Wrap => min + (value - min).rem_euclid(span + 1),
+        }
+    }
+}
+
+// The bounds of the world in global vertex coordinates
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Display, Reflect)]
+#[display(fmt = "{} to {} ({})", min, max, behavior)]
+pub struct WorldEdges {
+    pub min: Vertex,
+    pub max: Vertex,
+    pub behavior: EdgeBehavior,
+}
+
+impl WorldEdges {
+    pub fn new(min: Vertex, max: Vertex, behavior: EdgeBehavior) -> Self {
+        Self { min, max, behavior }
+    }
+
+    pub fn apply(&self, global_vertex: Vertex) -> Vertex {
+        Vertex {
+            x: self.behavior.apply(global_vertex.x, self.min.x, self.max.x),
+            z: self.behavior.apply(global_vertex.z, self.min.z, self.max.z),
+        }
+    }
+}
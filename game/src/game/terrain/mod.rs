@@ -2,49 +2,150 @@ use ::noise::NoiseFn;
 
 use bevy::{prelude::*, utils::HashMap};
 
+mod adaptive;
+pub use adaptive::*;
+
 mod chunk;
 pub use chunk::*;
 
+mod dedup;
+
+mod deformation;
+pub use deformation::*;
+
+mod edges;
+pub use edges::*;
+
+mod neighbors;
+pub use neighbors::*;
+
 mod noise;
 pub use noise::*;
 
+mod palette;
+pub use palette::*;
+
 mod plugin;
 pub use plugin::*;
 
+mod preload;
+pub use preload::*;
+
+#[cfg(feature = "terrain_profiling")]
+mod profiling;
+#[cfg(feature = "terrain_profiling")]
+pub use profiling::*;
+
+mod queries;
+
+mod seams;
+pub use seams::*;
+
+mod transition;
+pub use transition::*;
+
 use crate::{Chunk, Level, TextureAssets, Vertex};
 
 #[derive(Clone, Debug, Default)]
 #[derive(Component)]
 pub struct Terrain {
     pub chunk_entities: HashMap<Vertex, Vec<Entity>>,
+    // the `AdaptiveQuadSize` band each chunk was generated with
+    pub chunk_bands: HashMap<Vertex, u32>,
+    // meshes generated ahead of time for chunks that are not in play yet, with their band
+    pub preloaded_meshes: HashMap<Vertex, (u32, Handle<Mesh>)>,
+    // the dents reaching into each chunk, pressed in again whenever the chunk is regenerated
+    pub dents: HashMap<Vertex, Vec<TerrainDent>>,
 }
 
 impl Terrain {
     pub fn new() -> Self {
         Self {
             chunk_entities: HashMap::new(),
+            chunk_bands: HashMap::new(),
+            preloaded_meshes: HashMap::new(),
+            dents: HashMap::new(),
         }
     }
 
+    pub fn band_for_chunk(
+        origin: Vertex,
+        level: &Level,
+        adaptive: Option<&AdaptiveQuadSize>,
+    ) -> u32 {
+        adaptive.map_or(0, |adaptive| adaptive.band_for_chunk(origin, level))
+    }
+
+    pub fn build_chunk(
+        origin: Vertex,
+        band: u32,
+        level: &Level,
+        adaptive: Option<&AdaptiveQuadSize>,
+    ) -> TerrainChunk {
+        let chunk = match adaptive {
+            Some(adaptive) => adaptive.chunk(origin, band, level),
+            None => Chunk {
+                quad_size: level.quad_size,
+                size: level.chunk_size,
+                origin,
+            },
+        };
+        let noise_scale = chunk.quad_size / level.quad_size;
+        TerrainChunk::new(chunk).with_noise_scale(noise_scale)
+    }
+
     pub fn name() -> Name {
         Name::new("Terrain")
     }
 
+    // remembers a dent for every chunk it reaches into, so it survives the chunk being regenerated
+    pub fn record_dent(&mut self, origin: Vertex, dent: TerrainDent) {
+        self.dents.entry(origin).or_default().push(dent);
+    }
+
+    // presses the dents recorded for a chunk into its newly generated mesh, before its collider
+    // is computed from it
+    fn reapply_dents(
+        &self,
+        terrain_chunk: &TerrainChunk,
+        mesh: &Handle<Mesh>,
+        meshes: &mut Assets<Mesh>,
+    ) {
+        let Some(dents) = self.dents.get(&terrain_chunk.chunk.origin) else {
+            return;
+        };
+        let Some(mesh) = meshes.get_mut(mesh) else {
+            return;
+        };
+        for dent in dents {
+            terrain_chunk.apply_dent(mesh, dent);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn update(
         &mut self,
         level: &Level,
         noise: &impl NoiseFn<f64, 2>,
         commands: &mut Commands,
         textures: &TextureAssets,
+        palette: Option<&TerrainColorPalette>,
+        adaptive: Option<&AdaptiveQuadSize>,
         meshes: &mut Assets<Mesh>,
         materials: &mut Assets<StandardMaterial>,
     ) {
-        // remove out-of-bounds chunks
+        let chunk_bands = level
+            .chunks_in_play
+            .iter()
+            .map(|origin| (*origin, Self::band_for_chunk(*origin, level, adaptive)))
+            .collect::<HashMap<_, _>>();
+
+        // remove out-of-bounds chunks and chunks that have changed band
         let chunks_to_remove = self
             .chunk_entities
             .iter()
             .filter_map(|(vertex, _)| {
-                if !level.chunks_in_play.contains(vertex) {
+                if chunk_bands.get(vertex) != self.chunk_bands.get(vertex) {
                     Some(*vertex)
                 } else {
                     None
@@ -53,6 +154,7 @@ impl Terrain {
             .collect::<Vec<_>>();
 
         for vertex in chunks_to_remove {
+            self.chunk_bands.remove(&vertex);
             if let Some(entities) = self.chunk_entities.remove(&vertex) {
                 for entity in entities {
                     commands.entity(entity).despawn();
@@ -60,19 +162,28 @@ impl Terrain {
             }
         }
 
-        // spawn missing in-bounds chunks
-        for origin in level.chunks_in_play.iter() {
-            if !self.chunk_entities.contains_key(origin) {
-                let chunk = Chunk {
-                    quad_size: level.quad_size,
-                    size: level.chunk_size,
-                    origin: *origin,
+        // spawn missing in-bounds chunks, using preloaded meshes where they are ready
+        for (origin, band) in chunk_bands {
+            if !self.chunk_entities.contains_key(&origin) {
+                let terrain_chunk = Self::build_chunk(origin, band, level, adaptive);
+                let mesh = match self.preloaded_meshes.remove(&origin) {
+                    Some((preloaded_band, mesh)) if preloaded_band == band => mesh,
+                    _ => meshes.add(match palette {
+                        Some(palette) => terrain_chunk.generate_colored_mesh(noise, palette),
+                        None => terrain_chunk.generate_mesh(noise),
+                    }),
                 };
-                let chunk_bundle =
-                    TerrainChunk::new(chunk).to_bundle(noise, textures, meshes, materials);
-                let chunk_entity = commands.spawn(chunk_bundle).id();
-                self.chunk_entities.insert(*origin, vec![chunk_entity]);
+                self.reapply_dents(&terrain_chunk, &mesh, meshes);
+                let chunk_entity = commands
+                    .spawn(terrain_chunk.to_bundle_with_mesh(mesh, textures, palette, materials))
+                    .id();
+                self.chunk_entities.insert(origin, vec![chunk_entity]);
+                self.chunk_bands.insert(origin, band);
             }
         }
+
+        // forget preloaded meshes that have fallen out of range again
+        self.preloaded_meshes
+            .retain(|origin, _| level.chunks_to_preload.contains(origin));
     }
 }
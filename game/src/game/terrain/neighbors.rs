@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::{SeamSide, Terrain, Vertex};
+
+// The entities of the chunks bordering a chunk, kept up to date as chunks spawn and despawn
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Component)]
+pub struct TerrainChunkNeighbors {
+    pub north: Option<Entity>,
+    pub south: Option<Entity>,
+    pub east: Option<Entity>,
+    pub west: Option<Entity>,
+}
+
+impl TerrainChunkNeighbors {
+    pub fn get(&self, side: SeamSide) -> Option<Entity> {
+        match side {
+            SeamSide::North => self.north,
+            SeamSide::South => self.south,
+            SeamSide::East => self.east,
+            SeamSide::West => self.west,
+        }
+    }
+
+    pub fn set(&mut self, side: SeamSide, entity: Option<Entity>) {
+        match side {
+            SeamSide::North => self.north = entity,
+            SeamSide::South => self.south = entity,
+            SeamSide::East => self.east = entity,
+            SeamSide::West => self.west = entity,
+        }
+    }
+}
+
+impl Terrain {
+    // looks up the chunks currently spawned around the chunk at `origin`
+    pub fn neighbors_of(&self, origin: Vertex) -> TerrainChunkNeighbors {
+        let mut neighbors = TerrainChunkNeighbors::default();
+        for side in SeamSide::ALL {
+            let entity = self
+                .chunk_entities
+                .get(&(origin + side.neighbor_offset()))
+                .and_then(|entities| entities.first())
+                .copied();
+            neighbors.set(side, entity);
+        }
+        neighbors
+    }
+}
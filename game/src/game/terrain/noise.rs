@@ -1,12 +1,15 @@
 // code adapted from
 // https://github.com/Razaekel/noise-rs/blob/d79aa83cc5bab27ccab3c82cc9265add0bbeaa46/examples/complexplanet.rs
 
+use std::sync::Arc;
+
 use noise::{Billow, Blend, Fbm, MultiFractal, NoiseFn, Perlin, RidgedMulti, ScaleBias};
 
 use bevy::prelude::*;
 
+// shared so that chunk meshes can be generated on other threads
 #[derive(Resource)]
-pub struct TerrainNoise(Box<dyn NoiseFn<f64, 2> + Send + Sync>);
+pub struct TerrainNoise(Arc<dyn NoiseFn<f64, 2> + Send + Sync>);
 
 impl TerrainNoise {
     pub fn new(seed: u32) -> Self {
@@ -14,11 +17,15 @@ impl TerrainNoise {
     }
 
     pub fn from_noise(noise: impl NoiseFn<f64, 2> + Send + Sync + 'static) -> Self {
-        Self(Box::new(noise))
+        Self(Arc::new(noise))
     }
 
     pub fn get(&self) -> &dyn NoiseFn<f64, 2> {
-        &self.0
+        &*self.0
+    }
+
+    pub fn get_shared(&self) -> Arc<dyn NoiseFn<f64, 2> + Send + Sync> {
+        self.0.clone()
     }
 }
 
@@ -38,7 +45,7 @@ fn generate_terrain_noise(seed: u32) -> impl NoiseFn<f64, 2> {
     )
     .set_bias(1.0);
     let hilly_ridged_multi = ScaleBias::new(
-        RidgedMulti::<Perlin>::new(seed + 17)
+        RidgedMulti::<Perlin>::new(seed.wrapping_add(17))
             .set_frequency(2.0)
             .set_lacunarity(2.162109375)
             .set_octaves(1),
@@ -47,7 +54,7 @@ fn generate_terrain_noise(seed: u32) -> impl NoiseFn<f64, 2> {
     ScaleBias::new(Blend::new(
         hilly_billow,
         hilly_ridged_multi,
-        Fbm::<Perlin>::new(seed + 19).set_frequency(0.001),
+        Fbm::<Perlin>::new(seed.wrapping_add(19)).set_frequency(0.001),
     ))
     .set_scale(3.)
 }
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+// Colors terrain vertices by height when textures are not available.
+// Stops are (height, color) pairs; heights between stops are interpolated linearly.
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct TerrainColorPalette {
+    pub stops: Vec<(f32, Color)>,
+}
+
+impl TerrainColorPalette {
+    pub fn new(mut stops: Vec<(f32, Color)>) -> Self {
+        stops.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Self { stops }
+    }
+
+    pub fn sample(&self, height: f32) -> Color {
+        let Some(&(first_height, first_color)) = self.stops.first() else {
+            return Color::WHITE;
+        };
+        if height <= first_height {
+            return first_color;
+        }
+        for (&(low_height, low_color), &(high_height, high_color)) in
+            self.stops.iter().zip(self.stops.iter().skip(1))
+        {
+            if height <= high_height {
+                let t = (height - low_height) / (high_height - low_height);
+                let low = Vec4::from_array(low_color.as_linear_rgba_f32());
+                let high = Vec4::from_array(high_color.as_linear_rgba_f32());
+                let color = low.lerp(high, t);
+                return Color::rgba_linear(color.x, color.y, color.z, color.w);
+            }
+        }
+        self.stops.last().unwrap().1
+    }
+}
+
+impl Default for TerrainColorPalette {
+    fn default() -> Self {
+        Self::new(vec![
+            (-1., Color::rgb(0.32, 0.24, 0.16)),
+            (0.5, Color::rgb(0.28, 0.42, 0.18)),
+            (2., Color::rgb(0.42, 0.56, 0.24)),
+            (4., Color::rgb(0.55, 0.52, 0.45)),
+        ])
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{AppState, TerrainNoise, TextureAssets};
+use crate::{
+    AppState, Chunk, EdgeBehavior, PendingChunks, TerrainChunk, TerrainDeformation, TerrainNoise,
+    TextureAssets, Vertex, WorldEdges, WorldSeed,
+};
 
 mod systems;
 
@@ -9,13 +12,60 @@ pub struct TerrainPlugin;
 
 impl Plugin for TerrainPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            systems::update_terrain_mesh.run_if(
-                resource_exists::<TextureAssets>().and_then(resource_exists::<TerrainNoise>()),
-            ),
-        )
-        .add_systems(OnEnter(AppState::SpawningScene), systems::seed_noise)
-        .add_systems(Update, systems::attach_terrain);
+        app.register_type::<Vertex>()
+            .register_type::<Chunk>()
+            .register_type::<EdgeBehavior>()
+            .register_type::<WorldEdges>()
+            .register_type::<Option<WorldEdges>>()
+            .register_type::<(u32, f32)>()
+            .register_type::<Option<(u32, f32)>>()
+            .register_type::<TerrainChunk>()
+            .init_resource::<WorldSeed>()
+            .init_resource::<PendingChunks>()
+            .init_resource::<TerrainDeformation>()
+            .add_systems(
+                Update,
+                systems::update_terrain_mesh.run_if(
+                    resource_exists::<TextureAssets>().and_then(resource_exists::<TerrainNoise>()),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::preload_terrain_meshes.run_if(resource_exists::<TerrainNoise>()),
+                    systems::collect_preloaded_meshes,
+                ),
+            )
+            .add_systems(OnEnter(AppState::SpawningScene), systems::seed_noise)
+            .add_systems(
+                Update,
+                (
+                    systems::attach_terrain,
+                    (
+                        systems::update_chunk_neighbors,
+                        apply_deferred,
+                        systems::attach_seam_locks,
+                    )
+                        .chain(),
+                    systems::lock_seams,
+                    systems::attach_impact_velocity,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::start_material_transitions,
+                    systems::update_material_transitions,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (systems::dent_terrain, systems::record_impact_velocity)
+                    .chain()
+                    .run_if(in_state(AppState::Racing)),
+            );
+        #[cfg(feature = "terrain_profiling")]
+        app.add_systems(Update, systems::profile_terrain_chunks);
     }
 }
@@ -1,9 +1,21 @@
-use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
 
-use crate::{Level, Terrain, TerrainNoise, TextureAssets};
+use bevy::{prelude::*, tasks::futures_lite::future};
 
-pub(super) fn seed_noise(mut commands: Commands) {
-    commands.insert_resource(TerrainNoise::new(rand::random()));
+use crate::{
+    AdaptiveQuadSize, Cheese, ImpactVelocity, Level, MaterialTransition, PendingChunk,
+    PendingChunks, SeamLock, SeamSide, Terrain, TerrainChunk, TerrainChunkNeighbors,
+    TerrainColorPalette, TerrainDeformation, TerrainDent, TerrainNoise, TextureAssets, WorldSeed,
+};
+
+pub(super) fn seed_noise(
+    mut commands: Commands,
+    seed: Res<WorldSeed>,
+    mut pending: ResMut<PendingChunks>,
+) {
+    commands.insert_resource(TerrainNoise::new(seed.0));
+    // anything still generating was sampled from the previous noise
+    pending.0.clear();
 }
 
 pub(super) fn attach_terrain(mut commands: Commands, query: Query<Entity, Added<Level>>) {
@@ -12,11 +24,14 @@ pub(super) fn attach_terrain(mut commands: Commands, query: Query<Entity, Added<
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn update_terrain_mesh(
     mut commands: Commands,
     mut terrain_query: Query<(&mut Terrain, &Level)>,
     noise: Res<TerrainNoise>,
     textures: Res<TextureAssets>,
+    palette: Option<Res<TerrainColorPalette>>,
+    adaptive: Option<Res<AdaptiveQuadSize>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
@@ -26,8 +41,288 @@ pub(super) fn update_terrain_mesh(
             &noise.get(),
             &mut commands,
             &textures,
+            palette.as_deref(),
+            adaptive.as_deref(),
             &mut meshes,
             &mut materials,
         );
     }
 }
+
+pub(super) fn preload_terrain_meshes(
+    terrain_query: Query<(&Terrain, &Level)>,
+    noise: Res<TerrainNoise>,
+    palette: Option<Res<TerrainColorPalette>>,
+    adaptive: Option<Res<AdaptiveQuadSize>>,
+    mut pending: ResMut<PendingChunks>,
+) {
+    for (terrain, level) in terrain_query.iter() {
+        for origin in level.chunks_to_preload.iter() {
+            if terrain.chunk_entities.contains_key(origin)
+                || terrain.preloaded_meshes.contains_key(origin)
+                || pending.contains(origin)
+            {
+                continue;
+            }
+            let band = Terrain::band_for_chunk(*origin, level, adaptive.as_deref());
+            let chunk = Terrain::build_chunk(*origin, band, level, adaptive.as_deref());
+            let task = chunk.generate_mesh_async(noise.get_shared(), palette.as_deref().cloned());
+            pending.0.push(PendingChunk {
+                origin: *origin,
+                band,
+                task,
+            });
+        }
+    }
+}
+
+pub(super) fn collect_preloaded_meshes(
+    mut terrain_query: Query<(&mut Terrain, &Level)>,
+    mut pending: ResMut<PendingChunks>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let Ok((mut terrain, level)) = terrain_query.get_single_mut() else {
+        return;
+    };
+    // dropping a task cancels it
+    pending
+        .0
+        .retain(|pending| level.chunks_to_preload.contains(&pending.origin));
+    pending.0.retain_mut(|pending| {
+        let Some(mesh) = future::block_on(future::poll_once(&mut pending.task)) else {
+            return true;
+        };
+        terrain
+            .preloaded_meshes
+            .insert(pending.origin, (pending.band, meshes.add(mesh)));
+        false
+    });
+}
+
+// refreshes every chunk's neighbors whenever a chunk spawns or despawns
+pub(super) fn update_chunk_neighbors(
+    mut commands: Commands,
+    terrain_query: Query<&Terrain>,
+    added_query: Query<(), Added<TerrainChunk>>,
+    mut removed: RemovedComponents<TerrainChunk>,
+    chunk_query: Query<(Entity, &TerrainChunk, Option<&TerrainChunkNeighbors>)>,
+) {
+    let any_removed = removed.read().count() > 0;
+    if added_query.is_empty() && !any_removed {
+        return;
+    }
+    let Ok(terrain) = terrain_query.get_single() else {
+        return;
+    };
+    for (entity, chunk, current) in chunk_query.iter() {
+        let neighbors = terrain.neighbors_of(chunk.chunk.origin);
+        if current != Some(&neighbors) {
+            commands.entity(entity).insert(neighbors);
+        }
+    }
+}
+
+// runs after `update_chunk_neighbors`, so new chunks already know their neighbors
+pub(super) fn attach_seam_locks(
+    mut commands: Commands,
+    new_chunk_query: Query<(Entity, &TerrainChunkNeighbors), Added<TerrainChunk>>,
+    mesh_query: Query<&Handle<Mesh>, With<TerrainChunk>>,
+) {
+    for (entity, neighbors) in new_chunk_query.iter() {
+        let mut seam_lock = SeamLock::default();
+        for side in SeamSide::ALL {
+            let neighbor_mesh = neighbors
+                .get(side)
+                .and_then(|entity| mesh_query.get(entity).ok())
+                // weak, so that despawned neighbors free their meshes
+                .map(|mesh| mesh.clone_weak());
+            seam_lock.set(side, neighbor_mesh);
+        }
+        commands.entity(entity).insert(seam_lock);
+    }
+}
+
+pub(super) fn lock_seams(
+    query: Query<(&TerrainChunk, &Handle<Mesh>, &SeamLock), Added<SeamLock>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (chunk, mesh, seam_lock) in query.iter() {
+        for side in SeamSide::ALL {
+            if let Some(neighbor_mesh) = seam_lock.get(side) {
+                chunk.lock_seam(side, mesh, neighbor_mesh, &mut meshes);
+            }
+        }
+    }
+}
+
+pub(super) fn attach_impact_velocity(mut commands: Commands, query: Query<Entity, Added<Cheese>>) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(ImpactVelocity::default());
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(super) fn dent_terrain(
+    mut commands: Commands,
+    mut terrain_query: Query<&mut Terrain>,
+    mut collisions: EventReader<CollisionStarted>,
+    contacts: Res<Collisions>,
+    deformation: Res<TerrainDeformation>,
+    cheese_query: Query<(&LinearVelocity, &Mass, &ImpactVelocity), With<Cheese>>,
+    chunk_query: Query<(Entity, &TerrainChunk, &Position, &Rotation, &Handle<Mesh>)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for CollisionStarted(entity1, entity2) in collisions.read() {
+        let (chunk_entity, cheese_entity) = if chunk_query.contains(*entity1) {
+            (*entity1, *entity2)
+        } else {
+            (*entity2, *entity1)
+        };
+        let Ok((_, _, position, rotation, _)) = chunk_query.get(chunk_entity) else {
+            continue;
+        };
+        let Ok((velocity, mass, impact_velocity)) = cheese_query.get(cheese_entity) else {
+            continue;
+        };
+        if mass.0 < deformation.min_mass {
+            continue;
+        }
+        // the change in momentum across the physics step is the impulse of the impact
+        let impulse = mass.0 * (impact_velocity.0 - velocity.0).length();
+        if impulse < deformation.impulse_threshold {
+            continue;
+        }
+        // the dent is centered on the deepest point of contact on the terrain
+        let Some(pair) = contacts.get(chunk_entity, cheese_entity) else {
+            continue;
+        };
+        let Some(center) = pair
+            .manifolds
+            .iter()
+            .flat_map(|manifold| manifold.contacts.iter())
+            .max_by(|a, b| a.penetration.total_cmp(&b.penetration))
+            .map(|contact| {
+                if pair.entity1 == chunk_entity {
+                    contact.global_point1(position, rotation)
+                } else {
+                    contact.global_point2(position, rotation)
+                }
+            })
+        else {
+            continue;
+        };
+        let dent = TerrainDent {
+            center,
+            depth: ((impulse - deformation.impulse_threshold) * deformation.depth_per_impulse)
+                .min(deformation.max_depth),
+            radius: deformation.radius,
+        };
+        let Ok(mut terrain) = terrain_query.get_single_mut() else {
+            continue;
+        };
+        // dents near a border reach into the neighboring chunks too
+        for (entity, chunk, _, _, mesh) in chunk_query.iter() {
+            if !chunk.is_in_dent_reach(&dent) {
+                continue;
+            }
+            terrain.record_dent(chunk.chunk.origin, dent);
+            let Some(mesh) = meshes.get_mut(mesh) else {
+                continue;
+            };
+            if !chunk.apply_dent(mesh, &dent) {
+                continue;
+            }
+            // the cheese should roll through the dent, not over the original surface
+            if let Some(collider) = Collider::trimesh_from_bevy_mesh(mesh) {
+                commands.entity(entity).insert(collider);
+            }
+        }
+    }
+}
+
+pub(super) fn record_impact_velocity(
+    mut query: Query<(&LinearVelocity, &mut ImpactVelocity), With<Cheese>>,
+) {
+    for (velocity, mut impact_velocity) in query.iter_mut() {
+        impact_velocity.0 = velocity.0;
+    }
+}
+
+// gives each transition its own material to blend into, so the source is left untouched,
+// waiting until the source has loaded to copy it
+pub(super) fn start_material_transitions(
+    mut query: Query<(&mut MaterialTransition, &mut Handle<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (mut transition, mut material) in query.iter_mut() {
+        if transition.blended.is_some() {
+            continue;
+        }
+        let Some(from) = materials.get(&transition.from).cloned() else {
+            continue;
+        };
+        let blended = materials.add(from);
+        *material = blended.clone();
+        transition.blended = Some(blended);
+    }
+}
+
+pub(super) fn update_material_transitions(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut MaterialTransition,
+        &mut Handle<StandardMaterial>,
+    )>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    time: Res<Time>,
+) {
+    for (entity, mut transition, mut material) in query.iter_mut() {
+        // nothing is blended until the transition has its own copy of the source
+        let Some(blended) = transition.blended.clone() else {
+            continue;
+        };
+        transition.progress = if transition.duration > 0. {
+            transition.progress + time.delta_seconds() / transition.duration
+        } else {
+            1.
+        };
+        if transition.progress >= 1. {
+            *material = transition.to.clone();
+            commands.entity(entity).remove::<MaterialTransition>();
+            continue;
+        }
+        let (Some(from), Some(to)) = (
+            materials.get(&transition.from),
+            materials.get(&transition.to),
+        ) else {
+            continue;
+        };
+        let blend = MaterialTransition::blend(from, to, transition.progress);
+        if let Some(material) = materials.get_mut(&blended) {
+            *material = blend;
+        }
+    }
+}
+
+#[cfg(feature = "terrain_profiling")]
+pub(super) fn profile_terrain_chunks(
+    mut commands: Commands,
+    query: Query<(Entity, &TerrainChunk, &Handle<Mesh>), Added<TerrainChunk>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for (entity, chunk, mesh) in query.iter() {
+        let Some(mesh) = meshes.get_mut(mesh) else {
+            continue;
+        };
+        let size = chunk.chunk.size + crate::Vertex::new(1, 1);
+        let Some(timings) = crate::VertexTimingMap::take_from_mesh(mesh, size) else {
+            continue;
+        };
+        let image = images.add(timings.to_image());
+        commands
+            .entity(entity)
+            .insert((timings, crate::VertexTimingImage(image)));
+    }
+}
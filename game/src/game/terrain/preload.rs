@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use noise::NoiseFn;
+
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+
+use crate::{TerrainChunk, TerrainColorPalette, Vertex};
+
+// A chunk mesh being generated on the async compute pool
+#[derive(Debug)]
+pub struct PendingChunk {
+    pub origin: Vertex,
+    // the `AdaptiveQuadSize` band the mesh is generated with
+    pub band: u32,
+    pub task: Task<Mesh>,
+}
+
+// Meshes being generated for the ring of chunks just outside of play,
+// so that chunks entering play do not need to be generated on the spot
+#[derive(Debug, Default)]
+#[derive(Resource)]
+pub struct PendingChunks(pub Vec<PendingChunk>);
+
+impl PendingChunks {
+    pub fn contains(&self, origin: &Vertex) -> bool {
+        self.0.iter().any(|pending| pending.origin == *origin)
+    }
+}
+
+impl TerrainChunk {
+    /// generates the chunk's mesh on the async compute pool
+    ///
+    /// the task can run on any thread, so everything moved into it must be `Send`. the chunk and
+    /// palette are plain data, but the noise is shared with the main thread, so it is taken as an
+    /// `Arc` of a `Send + Sync` noise function. `generate_mesh` borrows `&impl NoiseFn` with no
+    /// such bounds, which is fine on the calling thread but cannot be moved into a task.
+    ///
+    /// noise that is not thread-safe, e.g. because it shares state through an `Rc`, is rejected:
+    ///
+    /// ```compile_fail
+    /// use std::{rc::Rc, sync::Arc};
+    ///
+    /// use noise::{NoiseFn, Perlin};
+    ///
+    /// use cheese_game::TerrainChunk;
+    ///
+    /// struct SharedNoise(Rc<Perlin>);
+    ///
+    /// impl NoiseFn<f64, 2> for SharedNoise {
+    ///     fn get(&self, point: [f64; 2]) -> f64 {
+    ///         self.0.get(point)
+    ///     }
+    /// }
+    ///
+    /// let noise = Arc::new(SharedNoise(Rc::new(Perlin::new(1))));
+    /// TerrainChunk::default().generate_mesh_async(noise, None);
+    /// ```
+    pub fn generate_mesh_async(
+        self,
+        noise: Arc<dyn NoiseFn<f64, 2> + Send + Sync>,
+        palette: Option<TerrainColorPalette>,
+    ) -> Task<Mesh> {
+        AsyncComputeTaskPool::get().spawn(async move {
+            let noise: &(dyn NoiseFn<f64, 2> + Send + Sync) = &*noise;
+            match palette {
+                Some(palette) => self.generate_colored_mesh(&noise, &palette),
+                None => self.generate_mesh(&noise),
+            }
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{MeshVertexAttribute, VertexAttributeValues},
+        render_resource::{Extent3d, TextureDimension, TextureFormat, VertexFormat},
+    },
+};
+
+use crate::Vertex;
+
+// The time spent sampling noise for each vertex of a chunk, in nanoseconds,
+// laid out in `Chunk::iter_by_row` order
+#[derive(Clone, Debug)]
+#[derive(Component)]
+pub struct VertexTimingMap {
+    // the number of vertices along each side of the chunk
+    pub size: Vertex,
+    pub nanos: Vec<f64>,
+}
+
+// The heat map of a chunk's `VertexTimingMap`, normalized so the slowest vertex is 1
+#[derive(Clone, Debug)]
+#[derive(Component)]
+pub struct VertexTimingImage(pub Handle<Image>);
+
+impl VertexTimingMap {
+    // the nanoseconds `TerrainChunk::generate_mesh` spent sampling each vertex's noise,
+    // recorded into the mesh so that meshes generated off the main thread carry it too
+    pub const ATTRIBUTE_NOISE_NANOS: MeshVertexAttribute =
+        MeshVertexAttribute::new("Vertex_NoiseNanos", 988540917, VertexFormat::Float32);
+
+    // removes the timings recorded into a chunk mesh, or None if its noise was never sampled
+    pub fn take_from_mesh(mesh: &mut Mesh, size: Vertex) -> Option<Self> {
+        let VertexAttributeValues::Float32(nanos) =
+            mesh.remove_attribute(Self::ATTRIBUTE_NOISE_NANOS)?
+        else {
+            return None;
+        };
+        Some(Self {
+            size,
+            nanos: nanos.into_iter().map(f64::from).collect(),
+        })
+    }
+
+    pub fn max(&self) -> f64 {
+        self.nanos.iter().copied().fold(0., f64::max)
+    }
+
+    // a single-channel float image with one texel per vertex
+    pub fn to_image(&self) -> Image {
+        let max = self.max().max(f64::EPSILON);
+        let data = self
+            .nanos
+            .iter()
+            .flat_map(|nanos| ((nanos / max) as f32).to_le_bytes())
+            .collect::<Vec<_>>();
+        Image::new(
+            Extent3d {
+                width: self.size.x as u32,
+                height: self.size.z as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::R32Float,
+        )
+    }
+}
@@ -0,0 +1,143 @@
+use noise::NoiseFn;
+
+use bevy::prelude::*;
+
+use crate::{TerrainChunk, TerrainNoise, Vertex};
+
+impl TerrainChunk {
+    // the terrain height at a mesh-space XZ position, bilinearly interpolated between the four
+    // surrounding vertices, or None outside the chunk's footprint
+    pub fn height_at(&self, local_xz: Vec2, noise: &impl NoiseFn<f64, 2>) -> Option<f32> {
+        self.height_at_with(local_xz, noise, self.blend_noise().as_ref())
+    }
+
+    fn height_at_with(
+        &self,
+        local_xz: Vec2,
+        noise: &impl NoiseFn<f64, 2>,
+        blend_noise: Option<&(TerrainNoise, f32)>,
+    ) -> Option<f32> {
+        let grid = local_xz / self.chunk.quad_size;
+        let size = Vec2::new(self.chunk.size.x as f32, self.chunk.size.z as f32);
+        if grid.x < 0. || grid.y < 0. || grid.x > size.x || grid.y > size.y {
+            return None;
+        }
+        let low = grid.floor().min(size - Vec2::ONE).max(Vec2::ZERO);
+        let fraction = grid - low;
+        let low = Vertex::new(low.x as i32, low.y as i32);
+        let height = |x: i32, z: i32| {
+            self.compute_vertex_position_with(Vertex::new(x, z), noise, blend_noise)
+                .0
+                .y
+        };
+        let near = lerp(height(low.x, low.z), height(low.x + 1, low.z), fraction.x);
+        let far = lerp(
+            height(low.x, low.z + 1),
+            height(low.x + 1, low.z + 1),
+            fraction.x,
+        );
+        Some(lerp(near, far, fraction.y))
+    }
+
+    // marches a world-space ray across the chunk and returns the world-space point where it first
+    // passes below the terrain, interpolated between the two samples on either side of the surface
+    pub fn ray_march_height(
+        &self,
+        ray_origin: Vec3,
+        ray_dir: Vec3,
+        max_steps: u32,
+        noise: &impl NoiseFn<f64, 2>,
+    ) -> Option<Vec3> {
+        let ray_dir = ray_dir.try_normalize()?;
+        let offset = self.compute_chunk_offset();
+        let origin = ray_origin - offset;
+        // never step further than a few quads, so narrow peaks are not skipped entirely,
+        // and never less than a fraction of one, so the march always makes progress
+        let min_step = self.chunk.quad_size.min_element() / 4.;
+        let max_step = self.chunk.quad_size.max_element() * 4.;
+
+        let blend_noise = self.blend_noise();
+
+        let mut distance = 0.;
+        let mut previous: Option<(f32, f32)> = None;
+        for _ in 0..max_steps {
+            let point = origin + ray_dir * distance;
+            let Some(height) =
+                self.height_at_with(Vec2::new(point.x, point.z), noise, blend_noise.as_ref())
+            else {
+                // outside the footprint, keep going in case the ray enters it later
+                previous = None;
+                distance += max_step;
+                continue;
+            };
+            let clearance = point.y - height;
+            if clearance <= 0. {
+                let hit_distance = match previous {
+                    Some((previous_distance, previous_clearance)) => {
+                        previous_distance
+                            + (distance - previous_distance) * previous_clearance
+                                / (previous_clearance - clearance)
+                    }
+                    // the ray started below the surface
+                    None => distance,
+                };
+                return Some(offset + origin + ray_dir * hit_distance);
+            }
+            previous = Some((distance, clearance));
+            // the step shrinks as the ray closes in on the surface and grows over open ground
+            distance += (clearance / 2.).clamp(min_step, max_step);
+        }
+        None
+    }
+
+    // finds the chunk vertex closest to a world position, returning its (x, z) grid index
+    // and its world position
+    pub fn nearest_vertex(
+        &self,
+        world_pos: Vec3,
+        noise: &impl NoiseFn<f64, 2>,
+    ) -> (u16, u16, Vec3) {
+        // brute force is fine for chunk-sized grids, but a KD-tree (e.g. `kiddo`) would
+        // scale better for large chunks
+        let blend_noise = self.blend_noise();
+        self.chunk
+            .iter_by_row()
+            .map(|vertex| {
+                let local = self.compute_vertex_position_with(vertex, noise, blend_noise.as_ref());
+                let position = self.local_to_world(local.0);
+                (vertex, position)
+            })
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(world_pos)
+                    .total_cmp(&b.distance_squared(world_pos))
+            })
+            .map(|(vertex, position)| (vertex.x as u16, vertex.z as u16, position))
+            .expect("chunks always have at least one vertex")
+    }
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use noise::Perlin;
+
+    use crate::Chunk;
+
+    use super::*;
+
+    #[test]
+    fn nearest_vertex_to_the_center_is_the_centroid() {
+        // hilltop chunks are flat, so the middle vertex lies exactly on the centroid
+        let chunk = Chunk::new((2, 1).into(), (8, 6).into(), Vec2::new(2., 3.));
+        let terrain_chunk = TerrainChunk::new(chunk);
+        let centroid = terrain_chunk.compute_centroid();
+
+        let (x, z, position) = terrain_chunk.nearest_vertex(centroid, &Perlin::new(1));
+
+        assert_eq!((x, z), (4, 3));
+        assert!(position.distance(centroid) < 1e-4);
+    }
+}
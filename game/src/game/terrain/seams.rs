@@ -0,0 +1,157 @@
+use derive_more::Display;
+
+use bevy::prelude::*;
+
+use crate::{Chunk, TerrainChunk, Vertex};
+
+// The sides of a chunk, in chunk coordinates:
+// north is +origin.z (uphill) and east is +origin.x
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Display)]
+pub enum SeamSide {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl SeamSide {
+    pub const ALL: [SeamSide; 4] = [
+        SeamSide::North,
+        SeamSide::South,
+        SeamSide::East,
+        SeamSide::West,
+    ];
+
+    pub fn opposite(self) -> Self {
+        match self {
+            SeamSide::North => SeamSide::South,
+            SeamSide::South => SeamSide::North,
+            SeamSide::East => SeamSide::West,
+            SeamSide::West => SeamSide::East,
+        }
+    }
+
+    // the offset from a chunk's origin to the origin of its neighbor on this side
+    pub fn neighbor_offset(self) -> Vertex {
+        match self {
+            SeamSide::North => Vertex::new(0, 1),
+            SeamSide::South => Vertex::new(0, -1),
+            SeamSide::East => Vertex::new(1, 0),
+            SeamSide::West => Vertex::new(-1, 0),
+        }
+    }
+
+    // the local vertices along this side of a chunk of the given size
+    pub fn border(self, size: Vertex) -> impl Iterator<Item = Vertex> {
+        let length = match self {
+            SeamSide::North | SeamSide::South => size.x,
+            SeamSide::East | SeamSide::West => size.z,
+        };
+        (0..=length).map(move |index| match self {
+            SeamSide::North => Vertex::new(index, 0),
+            SeamSide::South => Vertex::new(index, size.z),
+            SeamSide::East => Vertex::new(size.x, index),
+            SeamSide::West => Vertex::new(0, index),
+        })
+    }
+}
+
+// The meshes of the neighboring chunks whose border vertices are locked to this chunk's
+#[derive(Clone, Debug, Default)]
+#[derive(Component)]
+pub struct SeamLock {
+    pub north: Option<Handle<Mesh>>,
+    pub south: Option<Handle<Mesh>>,
+    pub east: Option<Handle<Mesh>>,
+    pub west: Option<Handle<Mesh>>,
+}
+
+impl SeamLock {
+    pub fn get(&self, side: SeamSide) -> Option<&Handle<Mesh>> {
+        match side {
+            SeamSide::North => self.north.as_ref(),
+            SeamSide::South => self.south.as_ref(),
+            SeamSide::East => self.east.as_ref(),
+            SeamSide::West => self.west.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, side: SeamSide, mesh: Option<Handle<Mesh>>) {
+        match side {
+            SeamSide::North => self.north = mesh,
+            SeamSide::South => self.south = mesh,
+            SeamSide::East => self.east = mesh,
+            SeamSide::West => self.west = mesh,
+        }
+    }
+}
+
+fn mesh_positions(meshes: &Assets<Mesh>, mesh: &Handle<Mesh>) -> Option<Vec<[f32; 3]>> {
+    meshes
+        .get(mesh)?
+        .attribute(Mesh::ATTRIBUTE_POSITION)?
+        .as_float3()
+        .map(|positions| positions.to_vec())
+}
+
+impl TerrainChunk {
+    // the chunk sharing the border on the given side, assuming it has the same size
+    pub fn neighbor(&self, side: SeamSide) -> TerrainChunk {
+        TerrainChunk {
+            chunk: Chunk::new(
+                self.chunk.origin + side.neighbor_offset(),
+                self.chunk.size,
+                self.chunk.quad_size,
+            ),
+            ..self.clone()
+        }
+    }
+
+    // moves the vertices shared with the neighbor on the given side to their average world
+    // position in both meshes, so that float error can never open a crack along the seam
+    pub fn lock_seam(
+        &self,
+        side: SeamSide,
+        mesh: &Handle<Mesh>,
+        neighbor_mesh: &Handle<Mesh>,
+        meshes: &mut Assets<Mesh>,
+    ) {
+        let Some(mut positions) = mesh_positions(meshes, mesh) else {
+            return;
+        };
+        let Some(mut neighbor_positions) = mesh_positions(meshes, neighbor_mesh) else {
+            return;
+        };
+        // chunks of different resolutions do not share every border vertex
+        if positions.len() != neighbor_positions.len() {
+            return;
+        }
+
+        let neighbor = self.neighbor(side);
+        let offset = self.compute_chunk_offset();
+        let neighbor_offset = neighbor.compute_chunk_offset();
+        let row_length = self.chunk.size.x + 1;
+        let position_index = |vertex: Vertex| (vertex.z * row_length + vertex.x) as usize;
+        for (vertex, neighbor_vertex) in side
+            .border(self.chunk.size)
+            .zip(side.opposite().border(self.chunk.size))
+        {
+            let (index, neighbor_index) = (position_index(vertex), position_index(neighbor_vertex));
+            let world_position = self.local_to_world(Vec3::from(positions[index]));
+            let neighbor_world_position =
+                neighbor.local_to_world(Vec3::from(neighbor_positions[neighbor_index]));
+            let average = (world_position + neighbor_world_position) / 2.;
+            positions[index] = (average - offset).to_array();
+            neighbor_positions[neighbor_index] = (average - neighbor_offset).to_array();
+        }
+
+        // writing through get_mut marks both meshes as modified
+        if let Some(mesh) = meshes.get_mut(mesh) {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        }
+        if let Some(neighbor_mesh) = meshes.get_mut(neighbor_mesh) {
+            neighbor_mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, neighbor_positions);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+// Crossfades a chunk from one material to another over `duration` seconds
+#[derive(Clone, Debug)]
+#[derive(Component)]
+pub struct MaterialTransition {
+    pub from: Handle<StandardMaterial>,
+    pub to: Handle<StandardMaterial>,
+    // from 0 to 1
+    pub progress: f32,
+    pub duration: f32,
+    // the chunk's own copy of `from` that the blend is written into, once `from` has loaded
+    pub blended: Option<Handle<StandardMaterial>>,
+}
+
+impl MaterialTransition {
+    pub fn new(
+        from: Handle<StandardMaterial>,
+        to: Handle<StandardMaterial>,
+        duration: f32,
+    ) -> Self {
+        Self {
+            from,
+            to,
+            progress: 0.,
+            duration,
+            blended: None,
+        }
+    }
+
+    // blends the interpolatable fields of the two materials, switching textures and other
+    // discrete settings over halfway through
+    pub fn blend(from: &StandardMaterial, to: &StandardMaterial, t: f32) -> StandardMaterial {
+        let mut blended = if t < 0.5 { from.clone() } else { to.clone() };
+        blended.base_color = lerp_color(from.base_color, to.base_color, t);
+        blended.emissive = lerp_color(from.emissive, to.emissive, t);
+        blended.perceptual_roughness = lerp(from.perceptual_roughness, to.perceptual_roughness, t);
+        blended.metallic = lerp(from.metallic, to.metallic, t);
+        blended.reflectance = lerp(from.reflectance, to.reflectance, t);
+        blended
+    }
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+fn lerp_color(from: Color, to: Color, t: f32) -> Color {
+    let from = Vec4::from(from.as_linear_rgba_f32());
+    let to = Vec4::from(to.as_linear_rgba_f32());
+    let blended = from.lerp(to, t);
+    Color::rgba_linear(blended.x, blended.y, blended.z, blended.w)
+}
@@ -0,0 +1,55 @@
+// global gizmo utilities
+
+use bevy::prelude::*;
+
+// Draws ballistic trajectory arcs as dotted lines
+#[derive(Clone, Copy, Debug)]
+pub struct TrajectoryArcGizmos {
+    pub color: Color,
+    // the number of steps in each visible dash, and in each gap between dashes
+    pub dash_steps: usize,
+}
+
+impl Default for TrajectoryArcGizmos {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            dash_steps: 3,
+        }
+    }
+}
+
+impl TrajectoryArcGizmos {
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            ..Default::default()
+        }
+    }
+
+    // draws the arc of a body launched from `origin` at `velocity`, integrated over `steps`
+    // steps of `dt` seconds under `gravity` (acceleration in -Y)
+    pub fn draw_arc(
+        &self,
+        origin: Vec3,
+        velocity: Vec3,
+        steps: usize,
+        dt: f32,
+        gravity: f32,
+        gizmos: &mut Gizmos,
+    ) {
+        let dash_steps = self.dash_steps.max(1);
+        let acceleration = Vec3::NEG_Y * gravity;
+        let mut position = origin;
+        let mut velocity = velocity;
+        for step in 0..steps {
+            let next_velocity = velocity + acceleration * dt;
+            let next_position = position + (velocity + next_velocity) / 2. * dt;
+            if (step / dash_steps) % 2 == 0 {
+                gizmos.line(position, next_position, self.color);
+            }
+            position = next_position;
+            velocity = next_velocity;
+        }
+    }
+}
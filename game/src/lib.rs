@@ -1,3 +1,5 @@
+use derive_more::Display;
+
 use bevy::{asset::AssetMetaCheck, prelude::*};
 
 mod assets;
@@ -6,9 +8,17 @@ pub use assets::*;
 mod game;
 pub use game::*;
 
+mod gizmos;
+pub use gizmos::*;
+
 mod menu;
 pub use menu::*;
 
+#[cfg(feature = "qr")]
+mod qr;
+#[cfg(feature = "qr")]
+pub use qr::*;
+
 mod scene;
 pub use scene::*;
 
@@ -19,6 +29,7 @@ mod ui;
 pub use ui::*;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, States)]
+#[derive(Display)]
 pub enum AppState {
     #[default]
     Loading,
@@ -48,6 +59,8 @@ pub fn run_app(canvas: Option<String>) {
             TerrainPlugin,
             ObstaclesPlugin,
             MenuPlugin,
+            #[cfg(feature = "qr")]
+            SeedQrPlugin,
         ))
         .run();
 }
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{button, AppState, FontAssets};
+use crate::{button, AppState, FontAssets, WorldSeed};
 
 const GAME_TITLE: &str = "Cheese Rolling: Forever";
 
@@ -10,11 +10,13 @@ pub(super) struct MenuUI;
 pub(super) struct PlayButton;
 
 pub(super) fn handle_play(
+    mut commands: Commands,
     interaction_query: Query<&Interaction, (Changed<Interaction>, With<PlayButton>)>,
     mut state: ResMut<NextState<AppState>>,
 ) {
     for interaction in interaction_query.iter() {
         if let Interaction::Pressed = interaction {
+            commands.insert_resource(WorldSeed::random());
             state.set(AppState::SpawningScene);
         }
     }
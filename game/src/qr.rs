@@ -0,0 +1,217 @@
+// share the current world seed as a QR code
+
+use qrcode::QrCode;
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+};
+
+use crate::{despawn_all_recursive, WorldSeed};
+
+pub struct SeedQrPlugin;
+
+impl Plugin for SeedQrPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                toggle_seed_qr,
+                despawn_all_recursive::<SeedQrUI>.run_if(close_requested),
+            ),
+        );
+        // there is no clipboard access from wasm without going through the browser
+        #[cfg(not(target_arch = "wasm32"))]
+        app.init_non_send_resource::<SeedClipboard>()
+            .add_systems(Update, copy_seed_to_clipboard.after(toggle_seed_qr));
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct SeedQrUI;
+
+// the blank border around the code that scanners need, in modules
+const QUIET_ZONE: usize = 4;
+
+// rasterizes the seed as a QR code with one byte per module, 0 for dark and 255 for light,
+// returning the side length of the raster along with it
+pub fn rasterize_seed_qr(seed: WorldSeed) -> Option<(usize, Vec<u8>)> {
+    let code = QrCode::new(seed.to_string()).ok()?;
+    let width = code.width();
+    let size = width + QUIET_ZONE * 2;
+    let mut data = vec![u8::MAX; size * size];
+    for (index, color) in code.to_colors().into_iter().enumerate() {
+        if color == qrcode::Color::Dark {
+            let (x, y) = (index % width + QUIET_ZONE, index / width + QUIET_ZONE);
+            data[y * size + x] = 0;
+        }
+    }
+    Some((size, data))
+}
+
+// the seed's QR code as a black and white image with one texel per module
+//
+// single-channel textures are sampled as red by the UI, so the raster is expanded to RGBA
+pub fn seed_qr_image(seed: WorldSeed) -> Option<Image> {
+    let (size, raster) = rasterize_seed_qr(seed)?;
+    let data = raster
+        .into_iter()
+        .flat_map(|value| [value, value, value, u8::MAX])
+        .collect();
+    let mut image = Image::new(
+        Extent3d {
+            width: size as u32,
+            height: size as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    // keep the modules crisp when the image is scaled up
+    image.sampler = ImageSampler::nearest();
+    Some(image)
+}
+
+fn close_requested(inputs: Res<Input<KeyCode>>) -> bool {
+    inputs.just_pressed(KeyCode::Escape)
+}
+
+fn toggle_seed_qr(
+    mut commands: Commands,
+    inputs: Res<Input<KeyCode>>,
+    seed: Option<Res<WorldSeed>>,
+    ui_query: Query<Entity, With<SeedQrUI>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if !inputs.just_pressed(KeyCode::F2) {
+        return;
+    }
+    if !ui_query.is_empty() {
+        for entity in ui_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    let Some(seed) = seed else {
+        return;
+    };
+    let Some(image) = seed_qr_image(*seed) else {
+        return;
+    };
+    let image = images.add(image);
+    commands
+        .spawn((
+            Name::new("Seed QR UI"),
+            SeedQrUI,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(16.),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.8).into(),
+                z_index: ZIndex::Global(10),
+                ..Default::default()
+            },
+        ))
+        .with_children(|builder| {
+            builder.spawn(ImageBundle {
+                style: Style {
+                    width: Val::Vmin(60.),
+                    height: Val::Vmin(60.),
+                    ..Default::default()
+                },
+                image: UiImage::new(image),
+                ..Default::default()
+            });
+            builder.spawn(TextBundle::from_section(
+                format!("Seed: {}", *seed),
+                TextStyle {
+                    font_size: 32.,
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+// the clipboard is kept open for the whole app, since on X11 and Wayland its contents are only
+// served for as long as the clipboard that set them is alive
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct SeedClipboard(Option<arboard::Clipboard>);
+
+// copies the seed whenever its QR code is shown
+#[cfg(not(target_arch = "wasm32"))]
+fn copy_seed_to_clipboard(
+    mut clipboard: NonSendMut<SeedClipboard>,
+    seed: Option<Res<WorldSeed>>,
+    ui_query: Query<(), Added<SeedQrUI>>,
+) {
+    if ui_query.is_empty() {
+        return;
+    }
+    let Some(seed) = seed else {
+        return;
+    };
+    if clipboard.0.is_none() {
+        match arboard::Clipboard::new() {
+            Ok(opened) => clipboard.0 = Some(opened),
+            Err(error) => {
+                warn!("failed to open the clipboard: {}", error);
+                return;
+            }
+        }
+    }
+    if let Some(Err(error)) = clipboard
+        .0
+        .as_mut()
+        .map(|opened| opened.set_text(seed.to_string()))
+    {
+        warn!("failed to copy the seed to the clipboard: {}", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_qr_image_has_a_light_quiet_zone() {
+        let seed = WorldSeed(54321);
+        let (size, raster) = rasterize_seed_qr(seed).unwrap();
+        let code = QrCode::new(seed.to_string()).unwrap();
+        assert_eq!(size, code.width() + QUIET_ZONE * 2);
+        for y in 0..size {
+            for x in 0..size {
+                let in_quiet_zone = x < QUIET_ZONE
+                    || y < QUIET_ZONE
+                    || x >= size - QUIET_ZONE
+                    || y >= size - QUIET_ZONE;
+                if in_quiet_zone {
+                    assert_eq!(raster[y * size + x], u8::MAX, "({x}, {y}) is not light");
+                }
+            }
+        }
+        // the top left finder pattern starts right inside the quiet zone
+        assert_eq!(raster[QUIET_ZONE * size + QUIET_ZONE], 0);
+
+        let image = seed_qr_image(seed).unwrap();
+        let extent = image.texture_descriptor.size;
+        assert_eq!((extent.width, extent.height), (size as u32, size as u32));
+        assert_eq!(
+            image.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        assert_eq!(image.data.len(), size * size * 4);
+    }
+}
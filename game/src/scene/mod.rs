@@ -3,7 +3,7 @@ use bevy_xpbd_3d::prelude::*;
 
 use crate::{
     despawn_all_recursive, AppState, Cheese, Level, Person, PlayerCamera, SceneAssets, Terrain,
-    TerrainChunk,
+    TerrainChunk, WorldSeed,
 };
 
 mod ui;
@@ -13,7 +13,8 @@ pub struct RaceScenePlugin;
 
 impl Plugin for RaceScenePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::SpawningScene), spawn_scene)
+        app.init_resource::<WorldSeed>()
+            .add_systems(OnEnter(AppState::SpawningScene), spawn_scene)
             .add_systems(
                 Update,
                 begin_countdown.run_if(in_state(AppState::SpawningScene)),
@@ -34,7 +35,13 @@ impl Plugin for RaceScenePlugin {
             .add_systems(OnEnter(AppState::GameOver), spawn_game_over_ui)
             .add_systems(
                 Update,
-                (handle_replay_action, handle_quit_action).run_if(in_state(AppState::GameOver)),
+                (
+                    fade_in_game_over_ui,
+                    handle_replay_action,
+                    handle_new_world_action,
+                    handle_quit_action,
+                )
+                    .run_if(in_state(AppState::GameOver)),
             )
             .add_systems(
                 OnExit(AppState::GameOver),
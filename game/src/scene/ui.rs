@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{button, AppState, HighScore, Score};
+use crate::{button, AppState, CheeseClock, HighScore, Score, WorldSeed};
 
 use super::RaceCountdown;
 
@@ -58,25 +58,44 @@ pub(super) fn track_countdown_ui(
 
 #[derive(Component)]
 pub(super) struct GameOverUI;
+// fades the game over backdrop in from transparent
+#[derive(Component)]
+pub(super) struct GameOverFade(Timer);
+
+impl GameOverFade {
+    const DURATION_SECS: f32 = 0.5;
+    const BACKDROP_ALPHA: f32 = 0.6;
+}
 
 pub(super) fn spawn_game_over_ui(
     mut commands: Commands,
     score: Res<Score>,
     high_score: Res<HighScore>,
+    clock: Res<CheeseClock>,
+    seed: Res<WorldSeed>,
 ) {
+    let text_style = TextStyle {
+        font_size: 24.0,
+        color: Color::rgb(0.02, 0.02, 0.1),
+        ..Default::default()
+    };
     commands
         .spawn((
             Name::new("Game Over UI"),
             GameOverUI,
+            GameOverFade(Timer::from_seconds(
+                GameOverFade::DURATION_SECS,
+                TimerMode::Once,
+            )),
             NodeBundle {
                 style: Style {
                     width: Val::Percent(100.0),
                     height: Val::Percent(100.0),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
-                    // background_color,
                     ..Default::default()
                 },
+                background_color: Color::rgba(0., 0., 0., 0.).into(),
                 ..Default::default()
             },
         ))
@@ -85,8 +104,8 @@ pub(super) fn spawn_game_over_ui(
                 .spawn((
                     NodeBundle {
                         style: Style {
-                            width: Val::Px(400.),
-                            height: Val::Px(400.),
+                            width: Val::Px(540.),
+                            height: Val::Px(520.),
                             flex_direction: FlexDirection::Column,
                             justify_content: JustifyContent::SpaceEvenly,
                             align_items: AlignItems::Center,
@@ -101,96 +120,112 @@ pub(super) fn spawn_game_over_ui(
                     Name::new("Game Over Panel"),
                 ))
                 .with_children(|builder| {
-                    builder.spawn(TextBundle::from_section(
-                        "Game Over!",
-                        TextStyle {
-                            font_size: 24.0,
-                            color: Color::rgb(0.02, 0.02, 0.1),
-
-                            ..Default::default()
-                        },
-                    ));
+                    builder.spawn(TextBundle::from_section("Game Over!", text_style.clone()));
                     builder.spawn(TextBundle::from_section(
                         "Your score is:",
-                        TextStyle {
-                            font_size: 24.0,
-                            color: Color::rgb(0.02, 0.02, 0.1),
-                            ..Default::default()
-                        },
+                        text_style.clone(),
                     ));
                     builder.spawn(TextBundle::from_section(
-                        format!("{:.0}", score.0),
-                        TextStyle {
-                            font_size: 24.0,
-                            color: Color::rgb(0.02, 0.02, 0.1),
-                            ..Default::default()
-                        },
+                        score.to_string(),
+                        text_style.clone(),
                     ));
                     if score.0 > high_score.0 {
                         builder.spawn(TextBundle::from_section(
                             "New high score!",
                             TextStyle {
                                 font_size: 28.0,
-                                color: Color::rgb(0.02, 0.02, 0.1),
-                                ..Default::default()
+                                ..text_style.clone()
                             },
                         ));
                     }
                     builder.spawn(TextBundle::from_section(
                         "Previous high score:",
-                        TextStyle {
-                            font_size: 24.0,
-                            color: Color::rgb(0.02, 0.02, 0.1),
-                            ..Default::default()
-                        },
+                        text_style.clone(),
                     ));
                     builder.spawn(TextBundle::from_section(
-                        format!("{:.0}", high_score.0),
-                        TextStyle {
-                            font_size: 24.0,
-                            color: Color::rgb(0.02, 0.02, 0.1),
-                            ..Default::default()
-                        },
+                        high_score.to_string(),
+                        text_style.clone(),
+                    ));
+                    builder.spawn(TextBundle::from_section(
+                        format!("Time: {}", *clock),
+                        text_style.clone(),
+                    ));
+                    builder.spawn(TextBundle::from_section(
+                        format!("Seed: {}", *seed),
+                        text_style,
                     ));
                     builder
-                        .spawn((Name::new("Replay Button"), ReplayButton, button()))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                Name::new("Replay Button Text"),
-                                TextBundle::from_section(
-                                    "Play Again",
-                                    TextStyle {
-                                        font_size: 28.0,
-                                        color: Color::rgb(0.9, 0.9, 0.9),
-                                        ..Default::default()
-                                    },
-                                ),
-                            ));
-                        });
-                    builder
-                        .spawn((Name::new("Quit Button"), QuitButton, button()))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                Name::new("Play Again Button Text"),
-                                TextBundle::from_section(
-                                    "Quit",
-                                    TextStyle {
-                                        font_size: 32.0,
-                                        color: Color::rgb(0.9, 0.9, 0.9),
-                                        ..Default::default()
-                                    },
-                                ),
-                            ));
+                        .spawn((
+                            Name::new("Game Over Buttons"),
+                            NodeBundle {
+                                style: Style {
+                                    column_gap: Val::Px(12.),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                        ))
+                        .with_children(|builder| {
+                            builder
+                                .spawn((Name::new("Replay Button"), ReplayButton, button()))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Name::new("Replay Button Text"),
+                                        button_text("Try Again"),
+                                    ));
+                                });
+                            builder
+                                .spawn((Name::new("New World Button"), NewWorldButton, button()))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Name::new("New World Button Text"),
+                                        button_text("New World"),
+                                    ));
+                                });
+                            builder
+                                .spawn((Name::new("Quit Button"), QuitButton, button()))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Name::new("Quit Button Text"),
+                                        button_text("Main Menu"),
+                                    ));
+                                });
                         });
                 });
         });
 }
 
+fn button_text(text: &str) -> TextBundle {
+    TextBundle::from_section(
+        text,
+        TextStyle {
+            font_size: 28.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+            ..Default::default()
+        },
+    )
+}
+
+pub(super) fn fade_in_game_over_ui(
+    mut query: Query<(&mut GameOverFade, &mut BackgroundColor)>,
+    time: Res<Time>,
+) {
+    for (mut fade, mut background) in query.iter_mut() {
+        fade.0.tick(time.delta());
+        background
+            .0
+            .set_a(fade.0.percent() * GameOverFade::BACKDROP_ALPHA);
+    }
+}
+
 #[derive(Component)]
 pub(super) struct ReplayButton;
 #[derive(Component)]
+pub(super) struct NewWorldButton;
+#[derive(Component)]
 pub(super) struct QuitButton;
 
+// replays the same world, since the seed is left untouched
 pub(super) fn handle_replay_action(
     interaction_query: Query<&Interaction, (Changed<Interaction>, With<ReplayButton>)>,
     mut state: ResMut<NextState<AppState>>,
@@ -202,6 +237,19 @@ pub(super) fn handle_replay_action(
     }
 }
 
+pub(super) fn handle_new_world_action(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<NewWorldButton>)>,
+    mut state: ResMut<NextState<AppState>>,
+) {
+    for interaction in interaction_query.iter() {
+        if let Interaction::Pressed = interaction {
+            commands.insert_resource(WorldSeed::random());
+            state.set(AppState::SpawningScene);
+        }
+    }
+}
+
 pub(super) fn handle_quit_action(
     interaction_query: Query<&Interaction, (Changed<Interaction>, With<QuitButton>)>,
     mut state: ResMut<NextState<AppState>>,